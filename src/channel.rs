@@ -0,0 +1,120 @@
+/// A MIDI channel. These are numbered 0 to 15 on the wire, though usually shown to users as 1 to 16.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "u8", into = "u8"))]
+pub struct Channel(u8);
+
+impl Channel {
+    const MAX: u8 = 0x0F;
+
+    pub fn get(&self) -> u8 {
+        self.0
+    }
+
+    /// Takes the channel from the low nibble of byte (e.g. a channel message status byte), ignoring the high nibble.
+    pub fn from_nibble(byte: u8) -> Self {
+        Self(byte & Self::MAX)
+    }
+
+    /// True for the General MIDI percussion channel: channel 10 as shown to users, which is 9 on the wire.
+    pub fn is_gm_percussion(&self) -> bool {
+        self.0 == 9
+    }
+
+    /// Every valid channel, in ascending order.
+    pub fn all() -> impl Iterator<Item = Channel> {
+        (0..=Self::MAX).map(Self)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ChannelError {
+    Overflow,
+}
+
+impl std::fmt::Display for ChannelError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChannelError::Overflow => write!(f, "channel must be at most 15"),
+        }
+    }
+}
+
+impl TryFrom<u8> for Channel {
+    type Error = ChannelError;
+
+    fn try_from(n: u8) -> Result<Self, Self::Error> {
+        if n > Self::MAX {
+            Err(Self::Error::Overflow)
+        } else {
+            Ok(Self(n))
+        }
+    }
+}
+
+impl From<Channel> for u8 {
+    fn from(channel: Channel) -> u8 {
+        channel.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use quickcheck::Arbitrary;
+    use quickcheck_macros::quickcheck;
+
+    use super::*;
+
+    impl Arbitrary for Channel {
+        fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+            Channel::from_nibble(u8::arbitrary(g))
+        }
+    }
+
+    #[test]
+    fn all_yields_sixteen_distinct_channels() {
+        let channels = Channel::all().collect::<Vec<Channel>>();
+        assert_eq!(channels.len(), 16);
+        assert_eq!(channels.iter().collect::<HashSet<&Channel>>().len(), 16);
+        assert_eq!(channels.first().map(Channel::get), Some(0));
+        assert_eq!(channels.last().map(Channel::get), Some(15));
+    }
+
+    #[test]
+    fn only_channel_index_nine_is_gm_percussion() {
+        assert!(Channel::from_nibble(9).is_gm_percussion());
+        assert!(!Channel::from_nibble(0).is_gm_percussion());
+        assert!(!Channel::from_nibble(10).is_gm_percussion());
+        assert_eq!(Channel::all().filter(Channel::is_gm_percussion).count(), 1);
+    }
+
+    #[quickcheck]
+    fn round_trip_from_and_to_u8_works(n: u8) {
+        if n > 0x0F {
+            assert_eq!(Channel::try_from(n), Err(ChannelError::Overflow));
+        } else {
+            assert_eq!(Channel::try_from(n).map(u8::from), Ok(n));
+        }
+    }
+
+    #[quickcheck]
+    fn from_nibble_ignores_high_nibble(n: u8) {
+        assert_eq!(Channel::from_nibble(n).get(), n & 0x0F);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_validates_range() {
+        assert_eq!(
+            serde_json::from_str::<Channel>("15").ok(),
+            Some(Channel::from_nibble(15))
+        );
+        assert!(serde_json::from_str::<Channel>("16").is_err());
+        assert_eq!(
+            serde_json::to_string(&Channel::from_nibble(15)).ok(),
+            Some(String::from("15"))
+        );
+    }
+}
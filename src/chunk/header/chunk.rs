@@ -10,23 +10,47 @@ pub struct Chunk {
 }
 
 impl Chunk {
-    #[allow(dead_code)]
-    pub fn new(format: Format, ntrks: NonZeroU16, division: Division) -> Self {
-        Self {
+    /// Fails if division could not be written out and read back, i.e. if it has more ticks per quarter note than fit
+    /// in 15 bits.
+    pub fn new(
+        format: Format,
+        ntrks: NonZeroU16,
+        division: Division,
+    ) -> Result<Self, DivisionError> {
+        division.to_u16()?;
+        Ok(Self {
             format,
             ntrks,
             division,
-        }
+        })
+    }
+
+    pub fn format(&self) -> Format {
+        self.format
+    }
+
+    pub fn ntrks(&self) -> NonZeroU16 {
+        self.ntrks
+    }
+
+    pub fn division(&self) -> &Division {
+        &self.division
     }
 }
 
 impl From<Chunk> for Vec<u8> {
     fn from(chunk: Chunk) -> Self {
+        Vec::from(&chunk)
+    }
+}
+
+impl From<&Chunk> for Vec<u8> {
+    fn from(chunk: &Chunk) -> Self {
         let payload_bytes = concat_vecs!(
             6;
             Vec::from(chunk.format),
             chunk.ntrks.get().to_be_bytes(),
-            Vec::from(chunk.division)
+            Vec::from(&chunk.division)
         );
 
         debug_assert!(
@@ -48,6 +72,7 @@ impl IntoIterator for Chunk {
     type IntoIter = std::vec::IntoIter<Self::Item>;
 
     fn into_iter(self) -> Self::IntoIter {
+        let format = Vec::<u8>::from(self.format);
         let output = vec![
             b'M',
             b'T',
@@ -57,8 +82,8 @@ impl IntoIterator for Chunk {
             0,
             0,
             6, // Length (u32 big endian) = 6
-            (self.format as u16).to_be_bytes()[0],
-            (self.format as u16).to_be_bytes()[1],
+            format[0],
+            format[1],
             self.ntrks.get().to_be_bytes()[0],
             self.ntrks.get().to_be_bytes()[1],
             self.division.high_byte(),
@@ -99,7 +124,7 @@ impl TryFrom<&[u8]> for Chunk {
                 format: Format::try_from(u16::from_be_bytes([value[8], value[9]]))?,
                 ntrks: NonZeroU16::new(u16::from_be_bytes([value[10], value[11]]))
                     .ok_or(ChunkError::NumberOfTracks)?,
-                division: Division::try_from(u16::from_be_bytes([value[12], value[13]]))?,
+                division: Division::try_from([value[12], value[13]])?,
             })
         }
     }
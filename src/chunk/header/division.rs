@@ -1,3 +1,4 @@
+use std::fmt::Display;
 use std::num::{NonZeroU16, NonZeroU8};
 
 use super::*;
@@ -13,7 +14,13 @@ pub enum Division {
 
 impl From<Division> for Vec<u8> {
     fn from(div: Division) -> Self {
-        match div {
+        Vec::from(&div)
+    }
+}
+
+impl From<&Division> for Vec<u8> {
+    fn from(div: &Division) -> Self {
+        match *div {
             Division::TicksPerQuarterNote(n) => {
                 debug_assert!(n.get() & Division::MARKER_BIT_MASK == 0);
                 Vec::from(n.get().to_be_bytes())
@@ -32,6 +39,25 @@ impl From<Division> for Vec<u8> {
     }
 }
 
+impl Display for Division {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Division::TicksPerQuarterNote(n) => write!(f, "{} PPQN", n),
+            Division::SubdivisionsOfASecond {
+                timecode_format,
+                ticks_per_frame,
+            } => {
+                write!(
+                    f,
+                    "{} fps, {} ticks/frame",
+                    timecode_format.frames_per_second(),
+                    ticks_per_frame
+                )
+            }
+        }
+    }
+}
+
 backed_enum!(
   pub enum SMPTETimecodeFormat(i8, SMPTETimecodeFormatError) {
     TwentyFour = -24,
@@ -41,13 +67,46 @@ backed_enum!(
   }
 );
 
+impl SMPTETimecodeFormat {
+    /// True only for ThirtyDropFrame (-29), which drops frame numbers to keep timecode in step with real time. This is
+    /// distinct from Thirty (-30), which is non-drop 30 frames per second.
+    pub fn is_drop_frame(&self) -> bool {
+        *self == SMPTETimecodeFormat::ThirtyDropFrame
+    }
+
+    /// The real-time frame rate. Drop-frame is given as its nominal 29.97 frames per second, although (like all
+    /// NTSC drop-frame timecode) it is really 30000/1001.
+    pub fn frames_per_second(&self) -> f64 {
+        match self {
+            SMPTETimecodeFormat::TwentyFour => 24.0,
+            SMPTETimecodeFormat::TwentyFive => 25.0,
+            SMPTETimecodeFormat::ThirtyDropFrame => 29.97,
+            SMPTETimecodeFormat::Thirty => 30.0,
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub enum DivisionError {
     TicksPerQuarterNoteMustBeGreaterThanZero,
+    TicksPerQuarterNoteMustFitInFifteenBits,
     TicksPerFrameMustBeGreaterThanZero,
     SMPTETimecodeFormatError(SMPTETimecodeFormatError),
 }
 
+/// The division is timecode-based (SMPTE), where only ticks per quarter note is supported.
+#[derive(Debug, PartialEq, Eq)]
+pub struct UnsupportedDivision;
+
+impl Display for UnsupportedDivision {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "SMPTE (subdivisions of a second) divisions are not supported; only ticks per quarter note is"
+        )
+    }
+}
+
 impl From<DivisionError> for ChunkError {
     fn from(e: DivisionError) -> Self {
         Self::Division(e)
@@ -80,12 +139,74 @@ impl TryFrom<u16> for Division {
     }
 }
 
+impl TryFrom<[u8; 2]> for Division {
+    type Error = DivisionError;
+
+    fn try_from(bytes: [u8; 2]) -> Result<Self, Self::Error> {
+        Self::try_from(u16::from_be_bytes(bytes))
+    }
+}
+
 impl Division {
     /// The on bit marks the format of this division:
     ///  0 => ticks per quarter note
     ///  1 => subdivisions of a second
     pub(super) const MARKER_BIT_MASK: u16 = 0b1000_0000_0000_0000;
 
+    /// A metrical division. ticks_per_quarter_note must be nonzero and fit in 15 bits, as the top bit of the division
+    /// marks it as SMPTE.
+    pub fn ticks_per_quarter_note(ticks_per_quarter_note: u16) -> Result<Self, DivisionError> {
+        if ticks_per_quarter_note & Self::MARKER_BIT_MASK != 0 {
+            Err(DivisionError::TicksPerQuarterNoteMustFitInFifteenBits)
+        } else {
+            NonZeroU16::new(ticks_per_quarter_note)
+                .map(Self::TicksPerQuarterNote)
+                .ok_or(DivisionError::TicksPerQuarterNoteMustBeGreaterThanZero)
+        }
+    }
+
+    /// A timecode-based division. ticks_per_frame must be nonzero.
+    pub fn smpte(
+        timecode_format: SMPTETimecodeFormat,
+        ticks_per_frame: u8,
+    ) -> Result<Self, DivisionError> {
+        NonZeroU8::new(ticks_per_frame)
+            .map(|ticks_per_frame| Self::SubdivisionsOfASecond {
+                timecode_format,
+                ticks_per_frame,
+            })
+            .ok_or(DivisionError::TicksPerFrameMustBeGreaterThanZero)
+    }
+
+    /// True if this division is metrical, i.e. measured in ticks per quarter note (and so dependent on tempo).
+    pub fn is_metrical(&self) -> bool {
+        matches!(self, Division::TicksPerQuarterNote(_))
+    }
+
+    /// True if this division is timecode-based, i.e. measured in subdivisions of a second.
+    pub fn is_smpte(&self) -> bool {
+        matches!(self, Division::SubdivisionsOfASecond { .. })
+    }
+
+    /// The ticks per quarter note, for consumers which only support metrical time.
+    pub fn require_ppqn(&self) -> Result<u16, UnsupportedDivision> {
+        match self {
+            Division::TicksPerQuarterNote(n) => Ok(n.get()),
+            Division::SubdivisionsOfASecond { .. } => Err(UnsupportedDivision),
+        }
+    }
+
+    /// The value of this division as it appears on the wire, which Division::try_from(u16) will always accept. Fails if
+    /// ticks per quarter note does not fit in 15 bits, as the top bit would mark the division as SMPTE.
+    pub fn to_u16(&self) -> Result<u16, DivisionError> {
+        match self {
+            Division::TicksPerQuarterNote(n) if n.get() & Self::MARKER_BIT_MASK != 0 => {
+                Err(DivisionError::TicksPerQuarterNoteMustFitInFifteenBits)
+            }
+            _ => Ok(u16::from_be_bytes([self.high_byte(), self.low_byte()])),
+        }
+    }
+
     pub(crate) fn high_byte(&self) -> u8 {
         match self {
             Division::TicksPerQuarterNote(n) => (!Self::MARKER_BIT_MASK & n.get()).to_be_bytes()[0],
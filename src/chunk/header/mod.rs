@@ -4,5 +4,7 @@ mod format;
 mod tests;
 
 pub use chunk::{Chunk, ChunkError};
-pub use division::{Division, DivisionError, SMPTETimecodeFormat, SMPTETimecodeFormatError};
+pub use division::{
+    Division, DivisionError, SMPTETimecodeFormat, SMPTETimecodeFormatError, UnsupportedDivision,
+};
 pub use format::{Format, FormatError};
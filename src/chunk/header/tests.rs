@@ -22,6 +22,128 @@ fn division_values_from_spec() {
     );
 }
 
+#[test]
+fn division_from_byte_array() {
+    assert_eq!(
+        Division::try_from([0xE2, 0x50]).expect("It's in the spec!"),
+        Division::SubdivisionsOfASecond {
+            timecode_format: SMPTETimecodeFormat::Thirty,
+            ticks_per_frame: NonZeroU8::new(80).expect("Value is non-zero"),
+        }
+    );
+    assert_eq!(
+        Division::try_from([0x00, 0x60]).expect("It's in the spec!"),
+        Division::TicksPerQuarterNote(NonZeroU16::new(96).expect("Value is non-zero"))
+    );
+}
+
+#[test]
+fn division_checked_constructors() {
+    assert_eq!(
+        Division::ticks_per_quarter_note(960),
+        Ok(Division::TicksPerQuarterNote(
+            NonZeroU16::new(960).expect("Value is non-zero")
+        ))
+    );
+    assert_eq!(
+        Division::ticks_per_quarter_note(0x7FFF).and_then(|division| division.to_u16()),
+        Ok(0x7FFF)
+    );
+    assert_eq!(
+        Division::ticks_per_quarter_note(0),
+        Err(DivisionError::TicksPerQuarterNoteMustBeGreaterThanZero)
+    );
+    assert_eq!(
+        Division::ticks_per_quarter_note(0x8000),
+        Err(DivisionError::TicksPerQuarterNoteMustFitInFifteenBits)
+    );
+    assert_eq!(
+        Division::smpte(SMPTETimecodeFormat::Thirty, 80),
+        Ok(Division::SubdivisionsOfASecond {
+            timecode_format: SMPTETimecodeFormat::Thirty,
+            ticks_per_frame: NonZeroU8::new(80).expect("Value is non-zero"),
+        })
+    );
+    assert_eq!(
+        Division::smpte(SMPTETimecodeFormat::Thirty, 0),
+        Err(DivisionError::TicksPerFrameMustBeGreaterThanZero)
+    );
+}
+
+#[test]
+fn division_kind_predicates() {
+    let metrical = Division::TicksPerQuarterNote(NonZeroU16::new(96).expect("Value is non-zero"));
+    assert!(metrical.is_metrical());
+    assert!(!metrical.is_smpte());
+
+    let smpte = Division::SubdivisionsOfASecond {
+        timecode_format: SMPTETimecodeFormat::TwentyFive,
+        ticks_per_frame: NonZeroU8::new(40).expect("Value is non-zero"),
+    };
+    assert!(smpte.is_smpte());
+    assert!(!smpte.is_metrical());
+}
+
+#[test]
+fn division_require_ppqn() {
+    assert_eq!(
+        Division::TicksPerQuarterNote(NonZeroU16::new(480).expect("Value is non-zero"))
+            .require_ppqn(),
+        Ok(480)
+    );
+    assert_eq!(
+        Division::SubdivisionsOfASecond {
+            timecode_format: SMPTETimecodeFormat::Thirty,
+            ticks_per_frame: NonZeroU8::new(80).expect("Value is non-zero"),
+        }
+        .require_ppqn(),
+        Err(UnsupportedDivision)
+    );
+}
+
+#[test]
+fn drop_frame_is_distinct_from_thirty() {
+    assert_eq!(
+        SMPTETimecodeFormat::try_from(-29),
+        Ok(SMPTETimecodeFormat::ThirtyDropFrame)
+    );
+    assert_eq!(
+        SMPTETimecodeFormat::try_from(-30),
+        Ok(SMPTETimecodeFormat::Thirty)
+    );
+    assert!(SMPTETimecodeFormat::ThirtyDropFrame.is_drop_frame());
+    assert!(!SMPTETimecodeFormat::Thirty.is_drop_frame());
+    assert_eq!(
+        SMPTETimecodeFormat::ThirtyDropFrame.frames_per_second(),
+        29.97
+    );
+    assert_eq!(SMPTETimecodeFormat::Thirty.frames_per_second(), 30.0);
+}
+
+#[test]
+fn division_display() {
+    assert_eq!(
+        Division::TicksPerQuarterNote(NonZeroU16::new(480).expect("Value is non-zero")).to_string(),
+        "480 PPQN"
+    );
+    assert_eq!(
+        Division::SubdivisionsOfASecond {
+            timecode_format: SMPTETimecodeFormat::TwentyFive,
+            ticks_per_frame: NonZeroU8::new(40).expect("Value is non-zero"),
+        }
+        .to_string(),
+        "25 fps, 40 ticks/frame"
+    );
+    assert_eq!(
+        Division::SubdivisionsOfASecond {
+            timecode_format: SMPTETimecodeFormat::ThirtyDropFrame,
+            ticks_per_frame: NonZeroU8::new(80).expect("Value is non-zero"),
+        }
+        .to_string(),
+        "29.97 fps, 80 ticks/frame"
+    );
+}
+
 #[quickcheck]
 fn division_ticks_per_quarter_note(value: u16) -> TestResult {
     if value == 0 {
@@ -165,6 +287,27 @@ impl Arbitrary for Division {
     }
 }
 
+#[quickcheck]
+fn division_borrowed_and_owned_serialization_match(division: Division) {
+    assert_eq!(Vec::from(&division), Vec::from(division));
+}
+
+#[quickcheck]
+fn division_roundtrips_through_u16(division: Division) {
+    assert_eq!(division.to_u16().and_then(Division::try_from), Ok(division));
+}
+
+#[test]
+fn division_to_u16_rejects_marker_bit_in_ppqn() {
+    for value in [0x8000, 0x81E0] {
+        assert_eq!(
+            Division::TicksPerQuarterNote(NonZeroU16::new(value).expect("Value is non-zero"))
+                .to_u16(),
+            Err(DivisionError::TicksPerQuarterNoteMustFitInFifteenBits)
+        );
+    }
+}
+
 #[derive(Clone, Debug)]
 struct FourteenBytes {
     data: [u8; 14],
@@ -183,7 +326,7 @@ impl Arbitrary for FourteenBytes {
 
 #[quickcheck]
 fn chunk_roundtrips(format: Format, ntrks: NonZeroU16, division: Division) {
-    let chunk = Chunk::new(format, ntrks, division);
+    let chunk = Chunk::new(format, ntrks, division).expect("Arbitrary divisions are valid");
     assert_eq!(
         Chunk::try_from(chunk.clone().into_iter().collect::<Vec<u8>>().as_slice()),
         Ok(chunk),
@@ -192,13 +335,64 @@ fn chunk_roundtrips(format: Format, ntrks: NonZeroU16, division: Division) {
 
 #[quickcheck]
 fn chunk_reprs_match(format: Format, ntrks: NonZeroU16, division: Division) {
-    let chunk = Chunk::new(format, ntrks, division);
+    let chunk = Chunk::new(format, ntrks, division).expect("Arbitrary divisions are valid");
     assert_eq!(
         chunk.clone().into_iter().collect::<Vec<u8>>(),
         Vec::from(chunk)
     );
 }
 
+#[test]
+fn chunk_rejects_division_with_marker_bit_in_ppqn() {
+    for value in [0x8000, 0x81E0] {
+        assert_eq!(
+            Chunk::new(
+                Format::SingleMultiChannelTrack,
+                NonZeroU16::new(1).expect("Value is non-zero"),
+                Division::TicksPerQuarterNote(NonZeroU16::new(value).expect("Value is non-zero")),
+            ),
+            Err(DivisionError::TicksPerQuarterNoteMustFitInFifteenBits)
+        );
+    }
+}
+
+#[quickcheck]
+fn chunk_borrowed_and_owned_serialization_match(
+    format: Format,
+    ntrks: NonZeroU16,
+    division: Division,
+) {
+    let chunk = Chunk::new(format, ntrks, division).expect("Arbitrary divisions are valid");
+    assert_eq!(Vec::from(&chunk), Vec::from(chunk));
+}
+
+#[test]
+fn chunk_serializes_every_format_through_format_bytes() {
+    let ntrks = NonZeroU16::new(1).expect("Value is non-zero");
+    let division = Division::TicksPerQuarterNote(NonZeroU16::new(96).expect("Value is non-zero"));
+    for (format, expected) in [
+        (Format::SingleMultiChannelTrack, [0, 0]),
+        (Format::OneOrMoreSimultaneousTracks, [0, 1]),
+        (Format::OneOrMoreIndependentTracks, [0, 2]),
+    ] {
+        let chunk =
+            Chunk::new(format, ntrks, division.clone()).expect("Arbitrary divisions are valid");
+        assert_eq!(Vec::from(format), expected);
+        assert_eq!(Vec::from(&chunk)[8..10], expected);
+        assert_eq!(chunk.into_iter().collect::<Vec<u8>>()[8..10], expected);
+    }
+}
+
+#[quickcheck]
+fn chunk_accessors_and_clone(format: Format, ntrks: NonZeroU16, division: Division) {
+    let chunk = Chunk::new(format, ntrks, division.clone()).expect("Arbitrary divisions are valid");
+    let cloned = chunk.clone();
+    assert_eq!(cloned.format(), format);
+    assert_eq!(cloned.ntrks(), ntrks);
+    assert_eq!(cloned.division(), &division);
+    assert_eq!(Vec::from(cloned), Vec::from(chunk));
+}
+
 #[quickcheck]
 /// This test always passes, as long as it doesn't panic.
 fn chunk_fuzz(value: FourteenBytes) -> TestResult {
@@ -14,3 +14,136 @@ impl From<ChunkType> for Vec<u8> {
         }
     }
 }
+
+/// Yields each track chunk in bytes (which should not include the header chunk) until the buffer is exhausted.
+/// Chunks of any type other than MTrk are skipped, as the spec requires. Iteration stops after the first error.
+/// Trailing bytes too short to be a chunk (e.g. padding after the last track) are ignored.
+pub fn parse_chunks(
+    bytes: &[u8],
+) -> impl Iterator<Item = Result<track::Chunk, track::ChunkError>> + '_ {
+    TrackChunks::new(bytes)
+}
+
+/// Returns whatever follows the chunk (of any type) at the start of bytes, using only its length field.
+pub(crate) fn skip_chunk(bytes: &[u8]) -> Result<&[u8], track::ChunkError> {
+    if bytes.len() < 8 {
+        Err(track::ChunkError::NotEnoughBytes)
+    } else {
+        let length = u32::from_be_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]) as usize;
+        if bytes.len() - 8 < length {
+            Err(track::ChunkError::ChunkLength)
+        } else {
+            Ok(&bytes[(8 + length)..])
+        }
+    }
+}
+
+pub(crate) struct TrackChunks<'a> {
+    remainder: &'a [u8],
+    failed: bool,
+    skip_unknown_chunks: bool,
+}
+
+impl<'a> TrackChunks<'a> {
+    pub(crate) fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            remainder: bytes,
+            failed: false,
+            skip_unknown_chunks: true,
+        }
+    }
+
+    /// When skip is false, a chunk of unknown type produces a track::ChunkError::ChunkType rather than being skipped.
+    pub(crate) fn skip_unknown_chunks(mut self, skip: bool) -> Self {
+        self.skip_unknown_chunks = skip;
+        self
+    }
+
+    /// The bytes not yet consumed. After an error, this starts at the chunk which failed to parse. Once iteration
+    /// finishes without error, this holds any trailing bytes too short to be a chunk.
+    pub(crate) fn remainder(&self) -> &'a [u8] {
+        self.remainder
+    }
+}
+
+impl<'a> Iterator for TrackChunks<'a> {
+    type Item = Result<track::Chunk, track::ChunkError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.failed && self.remainder.len() >= 8 {
+            if self.skip_unknown_chunks && self.remainder[0..4] != [b'M', b'T', b'r', b'k'] {
+                match skip_chunk(self.remainder) {
+                    Ok(remainder) => {
+                        self.remainder = remainder;
+                        continue;
+                    }
+                    Err(e) => {
+                        self.failed = true;
+                        return Some(Err(e));
+                    }
+                }
+            }
+
+            return Some(match track::Chunk::parse(self.remainder) {
+                Ok((chunk, remainder)) => {
+                    self.remainder = remainder;
+                    Ok(chunk)
+                }
+                Err(e) => {
+                    self.failed = true;
+                    Err(e)
+                }
+            });
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chunks_from_brandenburg_concerto() {
+        let tracks = parse_chunks(&crate::test_data::brandenburg::DATA[14..])
+            .collect::<Result<Vec<track::Chunk>, track::ChunkError>>()
+            .expect("The Brandenburg concerto is a valid file");
+        assert_eq!(tracks.len(), 11);
+        assert!(tracks
+            .iter()
+            .all(|track| track.data().ends_with(&[0xFF, 0x2F, 0x00])));
+    }
+
+    #[test]
+    fn unknown_chunk_types_are_skipped() {
+        let bytes = [
+            b'X', b'Y', b'Z', b'W', 0, 0, 0, 2, 0xAB, 0xCD, // Unknown chunk
+            b'M', b'T', b'r', b'k', 0, 0, 0, 4, 0x00, 0xFF, 0x2F, 0x00,
+        ];
+        assert_eq!(
+            parse_chunks(&bytes).collect::<Vec<_>>(),
+            vec![Ok(track::Chunk::new(vec![0x00, 0xFF, 0x2F, 0x00]))]
+        );
+    }
+
+    #[test]
+    fn trailing_padding_is_ignored() {
+        let bytes = [
+            b'M', b'T', b'r', b'k', 0, 0, 0, 4, 0x00, 0xFF, 0x2F, 0x00, // Track
+            0x00, 0x00, 0x00, // Padding
+        ];
+        let mut chunks = TrackChunks::new(&bytes);
+        assert_eq!(chunks.next(), Some(Ok(track::Chunk::empty())));
+        assert_eq!(chunks.next(), None);
+        assert_eq!(chunks.remainder(), &[0x00, 0x00, 0x00]);
+    }
+
+    #[test]
+    fn iteration_stops_after_error() {
+        let bytes = [b'M', b'T', b'r', b'k', 0, 0, 0, 4, 0x00];
+        assert_eq!(
+            parse_chunks(&bytes).collect::<Vec<_>>(),
+            vec![Err(track::ChunkError::ChunkLength)]
+        );
+    }
+}
@@ -1 +1,290 @@
+use crate::u7::U7;
+use crate::vlq::Vlq;
 
+/// A track (MTrk) chunk. Events are not parsed yet, so the payload is kept as the raw bytes following the length.
+/// The length field is 32 bits, so a payload longer than u32::MAX bytes cannot be serialized; trying to panics rather
+/// than writing a truncated length.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chunk {
+    data: Vec<u8>,
+}
+
+impl Chunk {
+    pub fn new(data: Vec<u8>) -> Self {
+        Self { data }
+    }
+
+    /// A track containing only a zero delta-time EndOfTrack event: the smallest valid track.
+    pub fn empty() -> Self {
+        Self::new(vec![0x00, 0xFF, 0x2F, 0x00])
+    }
+
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Parses a single track chunk from the start of bytes, returning it along with the unconsumed remainder.
+    pub fn parse(bytes: &[u8]) -> Result<(Self, &[u8]), ChunkError> {
+        if bytes.len() < 8 {
+            Err(ChunkError::NotEnoughBytes)
+        } else if bytes[0..4] != [b'M', b'T', b'r', b'k'] {
+            Err(ChunkError::ChunkType)
+        } else {
+            let length = u32::from_be_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]) as usize;
+            if bytes.len() - 8 < length {
+                Err(ChunkError::ChunkLength)
+            } else {
+                Ok((
+                    Chunk {
+                        data: Vec::from(&bytes[8..(8 + length)]),
+                    },
+                    &bytes[(8 + length)..],
+                ))
+            }
+        }
+    }
+
+    /// Sets the track's SequenceNumber meta event to number. A SequenceNumber must come before any nonzero delta time
+    /// or transmittable event, so one is looked for (in either its two-byte or its empty form) only among the zero
+    /// delta-time meta events at the start of the track. If there is none, one is inserted at the very start.
+    pub fn set_sequence_number(&mut self, number: u16) {
+        let event = concat_vecs!([0x00, 0xFF, 0x00, 0x02], number.to_be_bytes());
+        let mut offset = 0;
+        while self.data.get(offset..(offset + 2)) == Some(&[0x00, 0xFF]) {
+            let Some(&meta_type) = self.data.get(offset + 2) else {
+                break;
+            };
+            let Some((length, rest)) = self.data.get((offset + 3)..).and_then(Vlq::parse) else {
+                break;
+            };
+            let end = self.data.len() - rest.len() + length.get() as usize;
+            if end > self.data.len() {
+                break;
+            }
+            if meta_type == 0x00 {
+                self.data.splice(offset..end, event);
+                return;
+            }
+            offset = end;
+        }
+        self.data.splice(0..0, event);
+    }
+
+    /// The length of this chunk once serialized, including the chunk type and length fields.
+    pub fn byte_len(&self) -> usize {
+        8 + self.data.len()
+    }
+
+    /// Parses a buffer consisting solely of track chunks, with no header chunk, such as a fragment copied out of a
+    /// larger file. Chunks of other types are skipped.
+    pub fn parse_all(bytes: &[u8]) -> Result<Vec<Self>, ChunkError> {
+        crate::chunk::parse_chunks(bytes).collect()
+    }
+}
+
+impl From<Chunk> for Vec<u8> {
+    fn from(chunk: Chunk) -> Self {
+        concat_vecs!(
+            8 + chunk.data.len();
+            Vec::<u8>::from(crate::chunk::ChunkType::Track),
+            length_field(chunk.data.len()),
+            chunk.data
+        )
+    }
+}
+
+impl From<&Chunk> for Vec<u8> {
+    fn from(chunk: &Chunk) -> Self {
+        concat_vecs!(
+            chunk.byte_len();
+            Vec::<u8>::from(crate::chunk::ChunkType::Track),
+            length_field(chunk.data.len()),
+            &chunk.data
+        )
+    }
+}
+
+/// The big-endian length field for a payload of length bytes.
+fn length_field(length: usize) -> [u8; 4] {
+    u32::try_from(length)
+        .expect("Track payloads longer than u32::MAX bytes cannot be serialized")
+        .to_be_bytes()
+}
+
+/// Combines the MSB and LSB of a 14-bit value, as sent in paired controller messages (e.g. bank select).
+pub fn combine_14bit(msb: U7, lsb: U7) -> u16 {
+    (msb.get() as u16) << 7 | lsb.get() as u16
+}
+
+/// Splits a 14-bit value into its MSB and LSB. Returns None if value is greater than 0x3FFF.
+pub fn split_14bit(value: u16) -> Option<(U7, U7)> {
+    if value > 0x3FFF {
+        None
+    } else {
+        Some((
+            U7::try_from((value >> 7) as u8).ok()?,
+            U7::try_from((value & 0x7F) as u8).ok()?,
+        ))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ChunkError {
+    NotEnoughBytes,
+    ChunkType,
+    ChunkLength,
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::quickcheck;
+
+    use super::*;
+
+    #[test]
+    fn parse_returns_remainder() {
+        let bytes = [
+            b'M', b'T', b'r', b'k', 0, 0, 0, 4, 0x00, 0xFF, 0x2F, 0x00, 0xAB,
+        ];
+        assert_eq!(
+            Chunk::parse(&bytes),
+            Ok((Chunk::new(vec![0x00, 0xFF, 0x2F, 0x00]), &bytes[12..]))
+        );
+    }
+
+    #[test]
+    fn parse_minimum_is_the_eight_byte_chunk_header() {
+        assert_eq!(
+            Chunk::parse(&[b'M', b'T', b'r', b'k', 0, 0, 0]),
+            Err(ChunkError::NotEnoughBytes)
+        );
+        assert_eq!(
+            Chunk::parse(&[b'M', b'T', b'r', b'k', 0, 0, 0, 0]),
+            Ok((Chunk::new(vec![]), &[][..]))
+        );
+        assert_eq!(
+            Chunk::parse(&[b'M', b'T', b'r', b'k', 0, 0, 0, 1, 0x00]),
+            Ok((Chunk::new(vec![0x00]), &[][..]))
+        );
+        assert_eq!(
+            Chunk::parse(&[b'M', b'T', b'r', b'k', 0, 0, 0, 2, 0x00]),
+            Err(ChunkError::ChunkLength)
+        );
+        assert_eq!(
+            Chunk::parse(&Vec::<u8>::from(Chunk::empty())),
+            Ok((Chunk::empty(), &[][..]))
+        );
+    }
+
+    #[test]
+    fn parse_rejects_bad_input() {
+        assert_eq!(Chunk::parse(b"MTrk"), Err(ChunkError::NotEnoughBytes));
+        assert_eq!(
+            Chunk::parse(&[b'M', b'T', b'h', b'd', 0, 0, 0, 0]),
+            Err(ChunkError::ChunkType)
+        );
+        assert_eq!(
+            Chunk::parse(&[b'M', b'T', b'r', b'k', 0, 0, 0, 1]),
+            Err(ChunkError::ChunkLength)
+        );
+    }
+
+    #[test]
+    fn parse_all_without_header() {
+        let first = Chunk::new(vec![0x00, 0xFF, 0x2F, 0x00]);
+        let second = Chunk::new(vec![0x00, 0x90, 0x3C, 0x40, 0x00, 0xFF, 0x2F, 0x00]);
+        let bytes = concat_vecs!(Vec::from(first.clone()), Vec::from(second.clone()));
+        assert_eq!(Chunk::parse_all(&bytes), Ok(vec![first, second]));
+    }
+
+    #[test]
+    fn fourteen_bit_values() {
+        assert_eq!(split_14bit(0), Some((U7::MIN, U7::MIN)));
+        assert_eq!(split_14bit(8192), Some((U7::MID, U7::MIN)));
+        assert_eq!(split_14bit(16383), Some((U7::MAX, U7::MAX)));
+        assert_eq!(split_14bit(16384), None);
+
+        assert_eq!(combine_14bit(U7::MIN, U7::MIN), 0);
+        assert_eq!(combine_14bit(U7::MID, U7::MIN), 8192);
+        assert_eq!(combine_14bit(U7::MAX, U7::MAX), 16383);
+    }
+
+    #[quickcheck]
+    fn fourteen_bit_roundtrips(msb: U7, lsb: U7) {
+        assert_eq!(split_14bit(combine_14bit(msb, lsb)), Some((msb, lsb)));
+    }
+
+    #[test]
+    fn empty_track_is_minimal() {
+        assert_eq!(
+            Vec::from(Chunk::empty()),
+            vec![b'M', b'T', b'r', b'k', 0, 0, 0, 4, 0x00, 0xFF, 0x2F, 0x00]
+        );
+    }
+
+    #[test]
+    fn sequence_number_is_inserted_or_replaced() {
+        let mut track = Chunk::empty();
+        track.set_sequence_number(0x0102);
+        assert_eq!(
+            track.data(),
+            [0x00, 0xFF, 0x00, 0x02, 0x01, 0x02, 0x00, 0xFF, 0x2F, 0x00]
+        );
+        track.set_sequence_number(7);
+        assert_eq!(
+            track.data(),
+            [0x00, 0xFF, 0x00, 0x02, 0x00, 0x07, 0x00, 0xFF, 0x2F, 0x00]
+        );
+    }
+
+    #[test]
+    fn short_sequence_number_is_replaced() {
+        let mut track = Chunk::new(vec![0x00, 0xFF, 0x00, 0x00, 0x00, 0xFF, 0x2F, 0x00]);
+        track.set_sequence_number(7);
+        assert_eq!(
+            track.data(),
+            [0x00, 0xFF, 0x00, 0x02, 0x00, 0x07, 0x00, 0xFF, 0x2F, 0x00]
+        );
+    }
+
+    #[test]
+    fn sequence_number_after_other_meta_events_is_replaced() {
+        let track_name = [0x00, 0xFF, 0x03, 0x01, b'A'];
+        let mut track = Chunk::new(concat_vecs!(
+            track_name,
+            [0x00, 0xFF, 0x00, 0x02, 0x00, 0x05],
+            [0x00, 0xFF, 0x2F, 0x00]
+        ));
+        track.set_sequence_number(7);
+        assert_eq!(
+            track.data(),
+            concat_vecs!(
+                track_name,
+                [0x00, 0xFF, 0x00, 0x02, 0x00, 0x07],
+                [0x00, 0xFF, 0x2F, 0x00]
+            )
+        );
+    }
+
+    #[test]
+    fn length_field_is_checked() {
+        assert_eq!(length_field(4), [0, 0, 0, 4]);
+        assert_eq!(length_field(u32::MAX as usize), [0xFF, 0xFF, 0xFF, 0xFF]);
+    }
+
+    #[cfg(target_pointer_width = "64")]
+    #[test]
+    #[should_panic(expected = "cannot be serialized")]
+    fn length_field_does_not_truncate() {
+        length_field(u32::MAX as usize + 1);
+    }
+
+    #[test]
+    fn serialization_roundtrips() {
+        let chunk = Chunk::new(vec![0x00, 0xFF, 0x2F, 0x00]);
+        let bytes = Vec::from(chunk.clone());
+        assert_eq!(bytes, Vec::from(&chunk));
+        assert_eq!(bytes.len(), chunk.byte_len());
+        assert_eq!(Chunk::parse(&bytes), Ok((chunk, &[][..])));
+    }
+}
@@ -0,0 +1,68 @@
+use crate::u7::U7;
+
+/// A control change controller number, with constants naming the common ones.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct Controller(U7);
+
+impl Controller {
+    pub const BANK_SELECT: Controller = Controller::from_u8(0);
+    pub const MODULATION: Controller = Controller::from_u8(1);
+    pub const BREATH: Controller = Controller::from_u8(2);
+    pub const FOOT: Controller = Controller::from_u8(4);
+    pub const PORTAMENTO_TIME: Controller = Controller::from_u8(5);
+    pub const DATA_ENTRY: Controller = Controller::from_u8(6);
+    pub const CHANNEL_VOLUME: Controller = Controller::from_u8(7);
+    pub const BALANCE: Controller = Controller::from_u8(8);
+    pub const PAN: Controller = Controller::from_u8(10);
+    pub const EXPRESSION: Controller = Controller::from_u8(11);
+    pub const BANK_SELECT_LSB: Controller = Controller::from_u8(32);
+    pub const DATA_ENTRY_LSB: Controller = Controller::from_u8(38);
+    pub const SUSTAIN: Controller = Controller::from_u8(64);
+    pub const PORTAMENTO: Controller = Controller::from_u8(65);
+    pub const SOSTENUTO: Controller = Controller::from_u8(66);
+    pub const SOFT_PEDAL: Controller = Controller::from_u8(67);
+    pub const NRPN_LSB: Controller = Controller::from_u8(98);
+    pub const NRPN_MSB: Controller = Controller::from_u8(99);
+    pub const RPN_LSB: Controller = Controller::from_u8(100);
+    pub const RPN_MSB: Controller = Controller::from_u8(101);
+
+    /// Only for defining the constants above; panics (at compile time) if n is out of range.
+    const fn from_u8(n: u8) -> Self {
+        match U7::new(n) {
+            Some(n) => Controller(n),
+            None => panic!("Controller numbers must fit in 7 bits"),
+        }
+    }
+
+    pub fn number(&self) -> U7 {
+        self.0
+    }
+}
+
+impl From<U7> for Controller {
+    fn from(n: U7) -> Self {
+        Controller(n)
+    }
+}
+
+impl From<Controller> for U7 {
+    fn from(controller: Controller) -> Self {
+        controller.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn constants_match_controller_numbers() {
+        assert_eq!(Controller::PAN.number().get(), 10);
+        assert_eq!(Controller::CHANNEL_VOLUME.number().get(), 7);
+        assert_eq!(Controller::SUSTAIN.number().get(), 64);
+        assert_eq!(
+            Controller::from(U7::try_from(10).expect("Value is in range")),
+            Controller::PAN
+        );
+    }
+}
@@ -0,0 +1,140 @@
+use crate::chunk::{header, track};
+
+/// An error encountered while parsing a whole file, along with the byte offset (from the start of the file) of the
+/// field or chunk which could not be parsed.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub offset: usize,
+    pub kind: ParseErrorKind,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    Header(header::ChunkError),
+    Track(track::ChunkError),
+}
+
+/// An error encountered while reading and parsing a file from disk.
+#[derive(Debug)]
+pub enum ReadError {
+    Io(std::io::Error),
+    Parse(ParseError),
+}
+
+impl From<std::io::Error> for ReadError {
+    fn from(e: std::io::Error) -> Self {
+        ReadError::Io(e)
+    }
+}
+
+impl From<ParseError> for ReadError {
+    fn from(e: ParseError) -> Self {
+        ReadError::Parse(e)
+    }
+}
+
+/// Something unusual that a lenient parse tolerated rather than rejecting.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseWarning {
+    /// This many bytes before the header chunk were skipped.
+    SkippedPrefix(usize),
+    /// This many bytes after the last chunk, too few to be a chunk themselves, were ignored.
+    TrailingBytes(usize),
+    /// The header chunk declared a payload this many bytes longer than the six the spec defines, which were skipped.
+    LongHeader(usize),
+}
+
+impl From<header::ChunkError> for ParseErrorKind {
+    fn from(e: header::ChunkError) -> Self {
+        ParseErrorKind::Header(e)
+    }
+}
+
+impl From<track::ChunkError> for ParseErrorKind {
+    fn from(e: track::ChunkError) -> Self {
+        ParseErrorKind::Track(e)
+    }
+}
+
+impl ParseError {
+    /// Builds a ParseError for an error in the header chunk, pointing at the offending field.
+    pub(crate) fn from_header(e: header::ChunkError) -> Self {
+        let offset = match e {
+            header::ChunkError::SliceSize | header::ChunkError::ChunkType => 0,
+            header::ChunkError::ChunkLength => 4,
+            header::ChunkError::Format(_) => 8,
+            header::ChunkError::NumberOfTracks => 10,
+            header::ChunkError::Division(_) => 12,
+        };
+        Self {
+            offset,
+            kind: e.into(),
+        }
+    }
+
+    /// Builds a ParseError for an error in the track chunk starting at chunk_offset, pointing at the offending field.
+    pub(crate) fn from_track(chunk_offset: usize, e: track::ChunkError) -> Self {
+        let offset = match e {
+            track::ChunkError::NotEnoughBytes | track::ChunkError::ChunkType => chunk_offset,
+            track::ChunkError::ChunkLength => chunk_offset + 4,
+        };
+        Self {
+            offset,
+            kind: e.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseErrorKind::Header(e) => match e {
+                header::ChunkError::SliceSize => write!(f, "header chunk is truncated"),
+                header::ChunkError::ChunkType => {
+                    write!(f, "file does not start with an MThd chunk")
+                }
+                header::ChunkError::ChunkLength => write!(f, "header chunk length is not 6"),
+                header::ChunkError::Format(_) => write!(f, "header chunk has an unknown format"),
+                header::ChunkError::NumberOfTracks => {
+                    write!(f, "header chunk declares zero tracks")
+                }
+                header::ChunkError::Division(_) => {
+                    write!(f, "header chunk has an invalid division")
+                }
+            },
+            ParseErrorKind::Track(e) => match e {
+                track::ChunkError::NotEnoughBytes => write!(f, "chunk is truncated"),
+                track::ChunkError::ChunkType => write!(f, "chunk is not a track (MTrk) chunk"),
+                track::ChunkError::ChunkLength => {
+                    write!(f, "chunk length runs past the end of the file")
+                }
+            },
+        }
+    }
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at byte {}", self.kind, self.offset)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl std::fmt::Display for ReadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReadError::Io(e) => write!(f, "could not read file: {}", e),
+            ReadError::Parse(e) => write!(f, "could not parse file: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ReadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ReadError::Io(e) => Some(e),
+            ReadError::Parse(e) => Some(e),
+        }
+    }
+}
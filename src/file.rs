@@ -0,0 +1,905 @@
+use std::num::NonZeroU16;
+use std::path::Path;
+
+use crate::chunk::{header, skip_chunk, track, TrackChunks};
+use crate::{ParseError, ParseWarning, ReadError};
+
+/// A standard MIDI file: a header chunk followed by some number of track chunks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SmfFile {
+    header: header::Chunk,
+    tracks: Vec<track::Chunk>,
+}
+
+impl SmfFile {
+    pub fn header(&self) -> &header::Chunk {
+        &self.header
+    }
+
+    pub fn tracks(&self) -> &[track::Chunk] {
+        &self.tracks
+    }
+
+    /// Reads and parses the file at path.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, ReadError> {
+        Ok(Self::try_from(std::fs::read(path)?.as_slice())?)
+    }
+
+    /// Appends track, setting the header's ntrks to the new number of tracks (which also corrects a header that
+    /// disagreed with the tracks actually parsed). Fails without changing anything if ntrks would overflow, or if this
+    /// is a format 0 file which already has its one track.
+    pub fn push_track(&mut self, track: track::Chunk) -> Result<(), BuildError> {
+        if self.header.format() == header::Format::SingleMultiChannelTrack
+            && !self.tracks.is_empty()
+        {
+            return Err(BuildError::MultipleTracksInSingleTrackFormat);
+        }
+        let ntrks = NonZeroU16::new(
+            u16::try_from(self.tracks.len() + 1).map_err(|_| BuildError::TooManyTracks)?,
+        )
+        .ok_or(BuildError::TooManyTracks)?;
+
+        self.header =
+            header::Chunk::new(self.header.format(), ntrks, self.header.division().clone())
+                .map_err(BuildError::InvalidDivision)?;
+        self.tracks.push(track);
+        Ok(())
+    }
+
+    /// The track holding tempo, time signature and other global meta events: the first track of a format 1 file, or
+    /// the only track of a format 0 file. Format 2 files have no conductor track, as each track is independent.
+    pub fn conductor_track(&self) -> Option<&track::Chunk> {
+        match self.header.format() {
+            header::Format::SingleMultiChannelTrack
+            | header::Format::OneOrMoreSimultaneousTracks => self.tracks.first(),
+            header::Format::OneOrMoreIndependentTracks => None,
+        }
+    }
+
+    /// The tracks holding performance events: every track after the conductor track in a format 1 file, or every
+    /// track in a format 0 or 2 file. The only track of a format 0 file is therefore also its conductor track.
+    pub fn instrument_tracks(&self) -> &[track::Chunk] {
+        match self.header.format() {
+            header::Format::OneOrMoreSimultaneousTracks => self.tracks.get(1..).unwrap_or(&[]),
+            header::Format::SingleMultiChannelTrack
+            | header::Format::OneOrMoreIndependentTracks => &self.tracks,
+        }
+    }
+
+    /// Sets each track's SequenceNumber to its index, as expected when several songs are assembled into one format 2
+    /// file. See track::Chunk::set_sequence_number for where the event is looked for or inserted.
+    pub fn renumber_sequences(&mut self) {
+        for (track, number) in self.tracks.iter_mut().zip(0..=u16::MAX) {
+            track.set_sequence_number(number);
+        }
+    }
+
+    /// The length of this file once serialized.
+    pub fn byte_len(&self) -> usize {
+        14 + self
+            .tracks
+            .iter()
+            .map(track::Chunk::byte_len)
+            .sum::<usize>()
+    }
+
+    /// The most bytes parse_skipping_prefix will skip while looking for the header chunk.
+    pub const MAX_SKIPPED_PREFIX: usize = 64;
+
+    /// Like SmfFile::try_from, but tolerates up to MAX_SKIPPED_PREFIX bytes of junk (e.g. a UTF-8 BOM) before the
+    /// header chunk, reporting a ParseWarning::SkippedPrefix if any were skipped. Error offsets are relative to the
+    /// start of bytes, including any skipped prefix.
+    pub fn parse_skipping_prefix(bytes: &[u8]) -> Result<(Self, Vec<ParseWarning>), ParseError> {
+        Self::parse_with(
+            bytes,
+            &ParseOptions {
+                max_skipped_prefix: Self::MAX_SKIPPED_PREFIX,
+                ..ParseOptions::default()
+            },
+        )
+    }
+
+    /// Parses a whole file, tolerating only the deviations from the spec which options allows, and reporting any
+    /// that were tolerated as warnings. Error offsets are relative to the start of bytes, including any skipped prefix.
+    pub fn parse_with(
+        bytes: &[u8],
+        options: &ParseOptions,
+    ) -> Result<(Self, Vec<ParseWarning>), ParseError> {
+        let mut warnings = Vec::new();
+
+        let prefix = bytes
+            .windows(4)
+            .take(options.max_skipped_prefix + 1)
+            .position(|window| window == [b'M', b'T', b'h', b'd'])
+            .unwrap_or(0);
+        if prefix > 0 {
+            warnings.push(ParseWarning::SkippedPrefix(prefix));
+        }
+
+        let header_bytes = &bytes[prefix..];
+        let header_error = |e| {
+            let e = ParseError::from_header(e);
+            ParseError {
+                offset: e.offset + prefix,
+                kind: e.kind,
+            }
+        };
+        let mut header_len = 14;
+        if options.allow_long_header
+            && header_bytes.len() >= 14
+            && header_bytes.starts_with(b"MThd")
+        {
+            let length = u32::from_be_bytes([
+                header_bytes[4],
+                header_bytes[5],
+                header_bytes[6],
+                header_bytes[7],
+            ]) as usize;
+            if length > 6 {
+                if header_bytes.len() - 8 < length {
+                    return Err(header_error(header::ChunkError::ChunkLength));
+                }
+                header_len = 8 + length;
+                warnings.push(ParseWarning::LongHeader(length - 6));
+            }
+        }
+        let header = if header_len > 14 {
+            // Only the six payload bytes the spec defines are parsed; the rest are skipped, as the spec requires.
+            let mut defined = [0u8; 14];
+            defined.copy_from_slice(&header_bytes[0..14]);
+            defined[4..8].copy_from_slice(&[0, 0, 0, 6]);
+            header::Chunk::try_from(&defined[..])
+        } else {
+            peek_header(header_bytes)
+        }
+        .map_err(header_error)?;
+
+        let mut tracks = Vec::new();
+        let mut chunks = TrackChunks::new(&bytes[(prefix + header_len)..])
+            .skip_unknown_chunks(options.skip_unknown_chunks);
+        while let Some(chunk) = chunks.next() {
+            tracks.push(
+                chunk.map_err(|e| {
+                    ParseError::from_track(bytes.len() - chunks.remainder().len(), e)
+                })?,
+            );
+        }
+
+        let trailing = chunks.remainder();
+        if !trailing.is_empty() {
+            if options.allow_trailing_bytes {
+                warnings.push(ParseWarning::TrailingBytes(trailing.len()));
+            } else {
+                return Err(ParseError::from_track(
+                    bytes.len() - trailing.len(),
+                    track::ChunkError::NotEnoughBytes,
+                ));
+            }
+        }
+
+        Ok((Self { header, tracks }, warnings))
+    }
+}
+
+impl From<&SmfFile> for Vec<u8> {
+    fn from(file: &SmfFile) -> Self {
+        let mut result = Vec::with_capacity(file.byte_len());
+        result.extend(Vec::<u8>::from(&file.header));
+        for track in &file.tracks {
+            result.extend(Vec::<u8>::from(track));
+        }
+
+        debug_assert!(
+            result.len() == file.byte_len(),
+            "byte_len() should exactly predict the serialized length, so that the buffer is never reallocated"
+        );
+
+        result
+    }
+}
+
+/// Controls which deviations from the spec SmfFile::parse_with tolerates. The default, which SmfFile::try_from uses,
+/// tolerates none of them. More options may be added, so start from ParseOptions::default() and set the fields you
+/// need.
+///
+/// ```
+/// let mut options = mjdi::ParseOptions::default();
+/// options.skip_unknown_chunks = false;
+/// options.allow_trailing_bytes = true;
+/// assert_ne!(options, mjdi::ParseOptions::default());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ParseOptions {
+    /// Skip chunks of any type other than MTrk after the header, as the spec requires. When false, they are rejected
+    /// with track::ChunkError::ChunkType instead. Defaults to true.
+    pub skip_unknown_chunks: bool,
+    /// Ignore bytes after the last chunk which are too few to be a chunk (e.g. padding to a word or block boundary),
+    /// with a ParseWarning::TrailingBytes. When false, they are rejected with track::ChunkError::NotEnoughBytes
+    /// instead. Defaults to false.
+    pub allow_trailing_bytes: bool,
+    /// Accept a header chunk declaring a payload longer than six bytes, skipping the extra bytes with a
+    /// ParseWarning::LongHeader, as the spec asks of readers in case later versions extend the header. When false, it is
+    /// rejected with header::ChunkError::ChunkLength instead. Defaults to false.
+    pub allow_long_header: bool,
+    /// Look this many bytes into the file for the header chunk, skipping anything before it with a
+    /// ParseWarning::SkippedPrefix. Defaults to 0, which requires the header chunk to start the file.
+    pub max_skipped_prefix: usize,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self {
+            skip_unknown_chunks: true,
+            allow_trailing_bytes: false,
+            allow_long_header: false,
+            max_skipped_prefix: 0,
+        }
+    }
+}
+
+/// Parses only the header chunk at the start of bytes, ignoring everything after it. This is much cheaper than
+/// parsing the whole file when only the format, number of tracks or division is needed.
+pub fn peek_header(bytes: &[u8]) -> Result<header::Chunk, header::ChunkError> {
+    header::Chunk::try_from(&bytes[0..bytes.len().min(14)])
+}
+
+/// Parses only the nth track chunk of a whole file (counting from zero), or returns None if there are not that many
+/// tracks. The header is not validated, and earlier chunks are skipped using their length fields alone, without
+/// copying their payloads.
+pub fn parse_nth_track(bytes: &[u8], n: usize) -> Result<Option<track::Chunk>, track::ChunkError> {
+    let mut remainder = bytes.get(14..).ok_or(track::ChunkError::NotEnoughBytes)?;
+    let mut index = 0;
+    while remainder.len() >= 8 {
+        if remainder.starts_with(b"MTrk") {
+            if index == n {
+                return track::Chunk::parse(remainder).map(|(chunk, _)| Some(chunk));
+            }
+            index += 1;
+        }
+        remainder = skip_chunk(remainder)?;
+    }
+    Ok(None)
+}
+
+/// If bytes is an RMID file (a standard MIDI file wrapped in a RIFF container, as in Windows .rmi files), returns the
+/// wrapped standard MIDI file, which can then be parsed as normal. Returns None for anything else, including a plain
+/// standard MIDI file or an RMID file with no (or a truncated) data chunk.
+pub fn unwrap_rmid(bytes: &[u8]) -> Option<&[u8]> {
+    if bytes.len() < 12 || bytes[0..4] != *b"RIFF" || bytes[8..12] != *b"RMID" {
+        return None;
+    }
+
+    let mut remainder = &bytes[12..];
+    while remainder.len() >= 8 {
+        // RIFF lengths are little endian, and chunks are padded to an even length.
+        let length =
+            u32::from_le_bytes([remainder[4], remainder[5], remainder[6], remainder[7]]) as usize;
+        let payload = remainder.get(8..)?.get(..length)?;
+        if remainder[0..4] == *b"data" {
+            return Some(payload);
+        }
+        remainder = remainder.get((8 + length + length % 2)..)?;
+    }
+    None
+}
+
+/// Builds an SmfFile, deriving the header's ntrks from the tracks added so the two cannot disagree.
+#[derive(Debug, Clone)]
+pub struct SmfBuilder {
+    format: header::Format,
+    division: header::Division,
+    tracks: Vec<track::Chunk>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum BuildError {
+    NoTracks,
+    TooManyTracks,
+    /// Format 0 files must contain exactly one track.
+    MultipleTracksInSingleTrackFormat,
+    /// The division could not be written out and read back; see header::Chunk::new.
+    InvalidDivision(header::DivisionError),
+}
+
+impl SmfBuilder {
+    pub fn new(format: header::Format, division: header::Division) -> Self {
+        Self {
+            format,
+            division,
+            tracks: Vec::new(),
+        }
+    }
+
+    /// Replaces the division with a metrical one of ppqn ticks per quarter note.
+    ///
+    /// ```
+    /// use mjdi::chunk::header::{Division, Format};
+    /// use mjdi::chunk::track;
+    /// use mjdi::SmfBuilder;
+    ///
+    /// let division = Division::ticks_per_quarter_note(96).expect("96 is valid");
+    /// let file = SmfBuilder::new(Format::SingleMultiChannelTrack, division)
+    ///     .ppqn(960)
+    ///     .expect("960 is a valid number of ticks per quarter note")
+    ///     .track(track::Chunk::empty())
+    ///     .build()
+    ///     .expect("The file has exactly one track");
+    /// assert_eq!(file.header().division().require_ppqn(), Ok(960));
+    /// ```
+    pub fn ppqn(mut self, ppqn: u16) -> Result<Self, header::DivisionError> {
+        self.division = header::Division::ticks_per_quarter_note(ppqn)?;
+        Ok(self)
+    }
+
+    /// Replaces the division with a timecode-based one of ticks_per_frame ticks per frame.
+    pub fn smpte(
+        mut self,
+        timecode_format: header::SMPTETimecodeFormat,
+        ticks_per_frame: u8,
+    ) -> Result<Self, header::DivisionError> {
+        self.division = header::Division::smpte(timecode_format, ticks_per_frame)?;
+        Ok(self)
+    }
+
+    pub fn track(mut self, track: track::Chunk) -> Self {
+        self.tracks.push(track);
+        self
+    }
+
+    /// Fails if no tracks were added, more than u16::MAX were (as ntrks could not represent that), more than one was
+    /// added to a format 0 file, or the division is out of range.
+    pub fn build(self) -> Result<SmfFile, BuildError> {
+        let ntrks = NonZeroU16::new(
+            u16::try_from(self.tracks.len()).map_err(|_| BuildError::TooManyTracks)?,
+        )
+        .ok_or(BuildError::NoTracks)?;
+        if self.format == header::Format::SingleMultiChannelTrack && ntrks.get() > 1 {
+            return Err(BuildError::MultipleTracksInSingleTrackFormat);
+        }
+
+        Ok(SmfFile {
+            header: header::Chunk::new(self.format, ntrks, self.division)
+                .map_err(BuildError::InvalidDivision)?,
+            tracks: self.tracks,
+        })
+    }
+}
+
+impl TryFrom<&[u8]> for SmfFile {
+    type Error = ParseError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        Self::parse_with(bytes, &ParseOptions::default()).map(|(file, _)| file)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::chunk::header::{ChunkError, FormatError};
+    use crate::ParseErrorKind;
+
+    use super::*;
+
+    #[test]
+    fn file_from_brandenburg_concerto() {
+        let file = SmfFile::try_from(&crate::test_data::brandenburg::DATA[..])
+            .expect("The Brandenburg concerto is a valid file");
+        assert_eq!(
+            file.header(),
+            &crate::test_data::brandenburg::expected_header()
+        );
+        assert_eq!(file.tracks().len(), 11);
+    }
+
+    #[test]
+    fn serialization_is_presized() {
+        let file = SmfFile::try_from(&crate::test_data::brandenburg::DATA[..])
+            .expect("The Brandenburg concerto is a valid file");
+        let bytes = Vec::from(&file);
+        assert_eq!(bytes.len(), file.byte_len());
+        assert_eq!(bytes.capacity(), file.byte_len());
+    }
+
+    /// Track payloads are kept as the exact bytes read, so no normalization (e.g. of running status or non-canonical
+    /// VLQs) happens on the way back out: every chunk must be reproduced bit-for-bit, at the same offset.
+    #[test]
+    fn brandenburg_roundtrips_bit_exact() {
+        let data = &crate::test_data::brandenburg::DATA[..];
+        let file = SmfFile::try_from(data).expect("The Brandenburg concerto is a valid file");
+
+        assert_eq!(Vec::from(file.header()).as_slice(), &data[0..14]);
+        let mut offset = 14;
+        for track in file.tracks() {
+            assert_eq!(
+                Vec::from(track).as_slice(),
+                &data[offset..(offset + track.byte_len())]
+            );
+            offset += track.byte_len();
+        }
+        assert_eq!(offset, data.len());
+        assert_eq!(Vec::from(&file).as_slice(), data);
+    }
+
+    #[test]
+    fn synthetic_file_roundtrips() {
+        let file = SmfFile::try_from(crate::test_data::synthetic::data().as_slice())
+            .expect("The synthetic file is valid");
+        assert_eq!(file.header(), &crate::test_data::synthetic::header());
+        assert_eq!(file.tracks(), &[crate::test_data::synthetic::track()]);
+    }
+
+    #[test]
+    fn builder_derives_ntrks() {
+        let division = header::Division::TicksPerQuarterNote(NonZeroU16::new(96).unwrap());
+        let file = SmfBuilder::new(
+            header::Format::OneOrMoreSimultaneousTracks,
+            division.clone(),
+        )
+        .track(crate::test_data::synthetic::track())
+        .track(crate::test_data::synthetic::track())
+        .track(crate::test_data::synthetic::track())
+        .build()
+        .expect("Three tracks is a valid number of tracks");
+        assert_eq!(
+            file.header(),
+            &header::Chunk::new(
+                header::Format::OneOrMoreSimultaneousTracks,
+                NonZeroU16::new(3).unwrap(),
+                division
+            )
+            .unwrap()
+        );
+        assert_eq!(file.tracks().len(), 3);
+    }
+
+    #[test]
+    fn builder_rejects_division_which_would_not_roundtrip() {
+        assert_eq!(
+            SmfBuilder::new(
+                header::Format::SingleMultiChannelTrack,
+                header::Division::TicksPerQuarterNote(
+                    NonZeroU16::new(0x81E0).expect("Value is non-zero")
+                ),
+            )
+            .track(track::Chunk::empty())
+            .build(),
+            Err(BuildError::InvalidDivision(
+                header::DivisionError::TicksPerQuarterNoteMustFitInFifteenBits
+            ))
+        );
+    }
+
+    #[test]
+    fn builder_division_methods_check_values() {
+        let builder = SmfBuilder::new(
+            header::Format::SingleMultiChannelTrack,
+            crate::test_data::synthetic::header().division().clone(),
+        );
+        assert_eq!(
+            builder
+                .clone()
+                .smpte(header::SMPTETimecodeFormat::ThirtyDropFrame, 80)
+                .map(|builder| builder.division.to_string()),
+            Ok(String::from("29.97 fps, 80 ticks/frame"))
+        );
+        assert_eq!(
+            builder.clone().ppqn(0).map(|builder| builder.division),
+            Err(header::DivisionError::TicksPerQuarterNoteMustBeGreaterThanZero)
+        );
+        assert_eq!(
+            builder
+                .smpte(header::SMPTETimecodeFormat::Thirty, 0)
+                .map(|builder| builder.division),
+            Err(header::DivisionError::TicksPerFrameMustBeGreaterThanZero)
+        );
+    }
+
+    #[test]
+    fn builder_rejects_invalid_track_counts() {
+        let division = header::Division::TicksPerQuarterNote(NonZeroU16::new(96).unwrap());
+        assert_eq!(
+            SmfBuilder::new(header::Format::OneOrMoreIndependentTracks, division.clone()).build(),
+            Err(BuildError::NoTracks)
+        );
+
+        let mut builder = SmfBuilder::new(header::Format::OneOrMoreIndependentTracks, division);
+        for _ in 0..=u16::MAX {
+            builder = builder.track(track::Chunk::new(vec![]));
+        }
+        assert_eq!(builder.build(), Err(BuildError::TooManyTracks));
+
+        assert_eq!(
+            SmfBuilder::new(
+                header::Format::SingleMultiChannelTrack,
+                header::Division::TicksPerQuarterNote(NonZeroU16::new(96).unwrap())
+            )
+            .track(crate::test_data::synthetic::track())
+            .track(crate::test_data::synthetic::track())
+            .build(),
+            Err(BuildError::MultipleTracksInSingleTrackFormat)
+        );
+    }
+
+    #[test]
+    fn junk_before_header_is_skipped_only_when_requested() {
+        let data = concat_vecs!(
+            vec![0xEF, 0xBB, 0xBF, 0x00],
+            crate::test_data::synthetic::data()
+        );
+        assert_eq!(
+            SmfFile::try_from(data.as_slice()),
+            Err(ParseError {
+                offset: 0,
+                kind: ParseErrorKind::Header(ChunkError::ChunkType),
+            })
+        );
+        assert_eq!(
+            SmfFile::parse_skipping_prefix(data.as_slice()),
+            Ok((
+                SmfFile::try_from(crate::test_data::synthetic::data().as_slice())
+                    .expect("The synthetic file is valid"),
+                vec![ParseWarning::SkippedPrefix(4)]
+            ))
+        );
+    }
+
+    #[test]
+    fn prefix_scan_is_bounded() {
+        let data = concat_vecs!(
+            vec![0u8; SmfFile::MAX_SKIPPED_PREFIX + 1],
+            crate::test_data::synthetic::data()
+        );
+        assert_eq!(
+            SmfFile::parse_skipping_prefix(data.as_slice()),
+            Err(ParseError {
+                offset: 0,
+                kind: ParseErrorKind::Header(ChunkError::ChunkType),
+            })
+        );
+        assert_eq!(
+            SmfFile::parse_skipping_prefix(crate::test_data::synthetic::data().as_slice())
+                .map(|(_, warnings)| warnings),
+            Ok(vec![])
+        );
+    }
+
+    #[test]
+    fn peek_header_ignores_tracks() {
+        assert_eq!(
+            peek_header(&crate::test_data::brandenburg::DATA[0..14]),
+            Ok(crate::test_data::brandenburg::expected_header())
+        );
+        assert_eq!(
+            peek_header(&crate::test_data::brandenburg::DATA[0..20]),
+            Ok(crate::test_data::brandenburg::expected_header())
+        );
+        assert_eq!(
+            peek_header(&crate::test_data::brandenburg::DATA[0..13]),
+            Err(ChunkError::SliceSize)
+        );
+    }
+
+    #[test]
+    fn conductor_track_depends_on_format() {
+        let division = header::Division::TicksPerQuarterNote(NonZeroU16::new(96).unwrap());
+        let conductor = track::Chunk::empty();
+        let instrument = crate::test_data::synthetic::track();
+        let build = |format| {
+            SmfBuilder::new(format, division.clone())
+                .track(conductor.clone())
+                .track(instrument.clone())
+                .build()
+                .expect("Two tracks is a valid number of tracks")
+        };
+
+        let single = SmfBuilder::new(header::Format::SingleMultiChannelTrack, division.clone())
+            .track(instrument.clone())
+            .build()
+            .expect("One track is a valid number of tracks");
+        assert_eq!(single.conductor_track(), Some(&instrument));
+        assert_eq!(
+            single.instrument_tracks(),
+            std::slice::from_ref(&instrument)
+        );
+
+        let simultaneous = build(header::Format::OneOrMoreSimultaneousTracks);
+        assert_eq!(simultaneous.conductor_track(), Some(&conductor));
+        assert_eq!(
+            simultaneous.instrument_tracks(),
+            std::slice::from_ref(&instrument)
+        );
+
+        let independent = build(header::Format::OneOrMoreIndependentTracks);
+        assert_eq!(independent.conductor_track(), None);
+        assert_eq!(independent.instrument_tracks(), &[conductor, instrument]);
+    }
+
+    #[test]
+    fn nth_track_matches_full_parse() {
+        let data = &crate::test_data::brandenburg::DATA[..];
+        let file = SmfFile::try_from(data).expect("The Brandenburg concerto is a valid file");
+        assert_eq!(parse_nth_track(data, 3), Ok(Some(file.tracks()[3].clone())));
+        assert_eq!(parse_nth_track(data, 10), Ok(file.tracks().last().cloned()));
+        assert_eq!(parse_nth_track(data, 11), Ok(None));
+        assert_eq!(
+            parse_nth_track(&data[0..10], 0),
+            Err(track::ChunkError::NotEnoughBytes)
+        );
+    }
+
+    #[test]
+    fn rmid_is_unwrapped() {
+        let smf = crate::test_data::synthetic::data();
+        let riff_payload = concat_vecs!(
+            b"RMID".to_vec(),
+            b"INFO".to_vec(),
+            3u32.to_le_bytes(),
+            vec![1, 2, 3, 0], // Odd length, so padded
+            b"data".to_vec(),
+            (smf.len() as u32).to_le_bytes(),
+            smf.clone()
+        );
+        let rmid = concat_vecs!(
+            b"RIFF".to_vec(),
+            (riff_payload.len() as u32).to_le_bytes(),
+            riff_payload
+        );
+
+        assert_eq!(unwrap_rmid(&rmid), Some(smf.as_slice()));
+        assert_eq!(
+            unwrap_rmid(&rmid).map(SmfFile::try_from),
+            Some(SmfFile::try_from(smf.as_slice()))
+        );
+        assert_eq!(unwrap_rmid(&smf), None);
+        assert_eq!(unwrap_rmid(&rmid[0..(rmid.len() - 1)]), None);
+    }
+
+    #[test]
+    fn from_path_roundtrips() {
+        let file = SmfFile::try_from(crate::test_data::synthetic::data().as_slice())
+            .expect("The synthetic file is valid");
+        let path = std::env::temp_dir().join(format!("mjdi-from-path-{}.mid", std::process::id()));
+        std::fs::write(&path, Vec::from(&file)).expect("The temporary directory is writable");
+        let result = SmfFile::from_path(&path);
+        std::fs::remove_file(&path).expect("We just created this file");
+        assert_eq!(result.ok(), Some(file));
+
+        assert!(matches!(
+            SmfFile::from_path(std::env::temp_dir().join("mjdi-this-file-does-not-exist.mid")),
+            Err(ReadError::Io(_))
+        ));
+    }
+
+    #[test]
+    fn push_track_bumps_ntrks() {
+        let mut file = SmfFile::try_from(&crate::test_data::brandenburg::DATA[..])
+            .expect("The Brandenburg concerto is a valid file");
+        let track = crate::test_data::synthetic::track();
+        assert_eq!(file.push_track(track.clone()), Ok(()));
+        assert_eq!(file.header().ntrks().get(), 12);
+        assert_eq!(file.tracks().last(), Some(&track));
+
+        let bytes = Vec::from(&file);
+        assert_eq!(&bytes[10..12], &[0, 12]);
+        assert_eq!(SmfFile::try_from(bytes.as_slice()), Ok(file));
+    }
+
+    #[test]
+    fn push_track_derives_ntrks_from_tracks() {
+        // A format 0 header claiming one track, but with no track chunks after it.
+        let mut file = SmfFile::try_from(&crate::test_data::synthetic::data()[0..14])
+            .expect("A header with no tracks still parses");
+        assert_eq!(file.header().ntrks().get(), 1);
+        assert_eq!(file.tracks().len(), 0);
+
+        assert_eq!(
+            file.push_track(crate::test_data::synthetic::track()),
+            Ok(())
+        );
+        assert_eq!(file.header().ntrks().get(), 1);
+        assert_eq!(file.tracks().len(), 1);
+
+        // A format 1 header claiming eleven tracks, followed by only two.
+        let header = header::Chunk::new(
+            header::Format::OneOrMoreSimultaneousTracks,
+            NonZeroU16::new(11).expect("Value is non-zero"),
+            crate::test_data::synthetic::header().division().clone(),
+        )
+        .expect("The division is valid");
+        let track = crate::test_data::synthetic::track();
+        let data = concat_vecs!(Vec::from(header), Vec::from(&track), Vec::from(&track));
+        let mut file =
+            SmfFile::try_from(data.as_slice()).expect("ntrks is not checked against the tracks");
+        assert_eq!(file.tracks().len(), 2);
+        assert_eq!(
+            file.push_track(crate::test_data::synthetic::track()),
+            Ok(())
+        );
+        assert_eq!(file.header().ntrks().get(), 3);
+    }
+
+    #[test]
+    fn push_track_rejects_second_track_in_format_0() {
+        let mut file = SmfFile::try_from(crate::test_data::synthetic::data().as_slice())
+            .expect("The synthetic file is valid");
+        assert_eq!(
+            file.push_track(crate::test_data::synthetic::track()),
+            Err(BuildError::MultipleTracksInSingleTrackFormat)
+        );
+        assert_eq!(file.header().ntrks().get(), 1);
+        assert_eq!(file.tracks().len(), 1);
+    }
+
+    #[test]
+    fn renumber_sequences_numbers_tracks_by_index() {
+        let mut numbered = track::Chunk::empty();
+        numbered.set_sequence_number(7);
+        let mut file = SmfBuilder::new(
+            header::Format::OneOrMoreIndependentTracks,
+            crate::test_data::synthetic::header().division().clone(),
+        )
+        .track(crate::test_data::synthetic::track())
+        .track(numbered)
+        .track(track::Chunk::empty())
+        .build()
+        .expect("Format 2 files may have several tracks");
+
+        file.renumber_sequences();
+        for (i, track) in file.tracks().iter().enumerate() {
+            assert_eq!(track.data()[0..6], [0x00, 0xFF, 0x00, 0x02, 0x00, i as u8]);
+        }
+        assert_eq!(
+            file.tracks()[0].data()[6..],
+            *crate::test_data::synthetic::track().data()
+        );
+        assert_eq!(file.tracks()[1].data()[6..], [0x00, 0xFF, 0x2F, 0x00]);
+    }
+
+    #[test]
+    fn trailing_padding_after_last_track_is_ignored_when_allowed() {
+        let data = concat_vecs!(crate::test_data::synthetic::data(), [0x00, 0x00, 0x00]);
+        let lenient = ParseOptions {
+            allow_trailing_bytes: true,
+            ..ParseOptions::default()
+        };
+        let (file, _) = SmfFile::parse_with(&data, &lenient).expect("Padding is tolerated");
+        assert_eq!(file.tracks(), &[crate::test_data::synthetic::track()]);
+        assert_eq!(parse_nth_track(&data, 1), Ok(None));
+    }
+
+    #[test]
+    fn parse_options_control_unknown_chunks() {
+        let data = concat_vecs!(
+            crate::test_data::synthetic::data(),
+            b"XFIH".to_vec(),
+            [0, 0, 0, 2, 0xAB, 0xCD]
+        );
+        assert_eq!(
+            SmfFile::parse_with(&data, &ParseOptions::default()).map(|(file, _)| file),
+            SmfFile::try_from(crate::test_data::synthetic::data().as_slice())
+        );
+
+        let strict = ParseOptions {
+            skip_unknown_chunks: false,
+            ..ParseOptions::default()
+        };
+        assert_eq!(
+            SmfFile::parse_with(&data, &strict),
+            Err(ParseError {
+                offset: crate::test_data::synthetic::data().len(),
+                kind: ParseErrorKind::Track(track::ChunkError::ChunkType),
+            })
+        );
+    }
+
+    #[test]
+    fn parse_options_control_trailing_bytes() {
+        let data = concat_vecs!(crate::test_data::synthetic::data(), [0x00, 0x00, 0x00]);
+        let error = Err(ParseError {
+            offset: data.len() - 3,
+            kind: ParseErrorKind::Track(track::ChunkError::NotEnoughBytes),
+        });
+        assert_eq!(SmfFile::parse_with(&data, &ParseOptions::default()), error);
+        assert_eq!(
+            SmfFile::try_from(data.as_slice()),
+            error.map(|(file, _)| file)
+        );
+
+        let lenient = ParseOptions {
+            allow_trailing_bytes: true,
+            ..ParseOptions::default()
+        };
+        assert_eq!(
+            SmfFile::parse_with(&data, &lenient).map(|(_, warnings)| warnings),
+            Ok(vec![ParseWarning::TrailingBytes(3)])
+        );
+    }
+
+    #[test]
+    fn parse_options_control_long_headers() {
+        let synthetic = crate::test_data::synthetic::data();
+        let data = concat_vecs!(
+            b"MThd".to_vec(),
+            [0, 0, 0, 8], // Length = 6 defined bytes + 2 extra
+            &synthetic[8..14],
+            [0xAB, 0xCD], // Extra bytes, to be skipped
+            &synthetic[14..]
+        );
+        let error = Err(ParseError {
+            offset: 4,
+            kind: ParseErrorKind::Header(ChunkError::ChunkLength),
+        });
+        assert_eq!(SmfFile::parse_with(&data, &ParseOptions::default()), error);
+
+        let lenient = ParseOptions {
+            allow_long_header: true,
+            ..ParseOptions::default()
+        };
+        assert_eq!(
+            SmfFile::parse_with(&data, &lenient),
+            Ok((
+                SmfFile::try_from(synthetic.as_slice()).expect("The synthetic file is valid"),
+                vec![ParseWarning::LongHeader(2)]
+            ))
+        );
+        assert_eq!(
+            SmfFile::parse_with(&data[0..15], &lenient),
+            error,
+            "A header length running past the end of the data is still rejected"
+        );
+    }
+
+    #[test]
+    fn header_error_offset_is_reported() {
+        let mut data = crate::test_data::brandenburg::DATA.to_vec();
+        data[9] = 3; // Format
+        assert_eq!(
+            SmfFile::try_from(data.as_slice()),
+            Err(ParseError {
+                offset: 8,
+                kind: ParseErrorKind::Header(ChunkError::Format(FormatError::InvalidValue)),
+            })
+        );
+    }
+
+    #[test]
+    fn errors_are_displayed_and_boxable() {
+        let mut data = crate::test_data::brandenburg::DATA.to_vec();
+        data[9] = 3; // Format
+        let error = SmfFile::try_from(data.as_slice()).expect_err("Format 3 does not exist");
+        assert_eq!(
+            error.to_string(),
+            "header chunk has an unknown format at byte 8"
+        );
+
+        let error: Box<dyn std::error::Error> = Box::new(ReadError::from(error));
+        assert_eq!(
+            error.to_string(),
+            "could not parse file: header chunk has an unknown format at byte 8"
+        );
+
+        let error: Box<dyn std::error::Error> = Box::new(
+            SmfFile::from_path(std::env::temp_dir().join("mjdi-this-file-does-not-exist.mid"))
+                .expect_err("The file does not exist"),
+        );
+        assert!(error
+            .source()
+            .is_some_and(|source| source.is::<std::io::Error>()));
+    }
+
+    #[test]
+    fn track_error_offset_is_reported() {
+        // The third track starts after the 14 byte header and two tracks of 8 + 27 and 8 + 24 bytes.
+        let third_track = 14 + 8 + 27 + 8 + 24;
+        let mut data = crate::test_data::brandenburg::DATA.to_vec();
+        data[(third_track + 4)..(third_track + 8)].copy_from_slice(&[0xFF; 4]); // Length
+        assert_eq!(
+            SmfFile::try_from(data.as_slice()),
+            Err(ParseError {
+                offset: third_track + 4,
+                kind: ParseErrorKind::Track(track::ChunkError::ChunkLength),
+            })
+        );
+    }
+}
@@ -1,9 +1,21 @@
 #[macro_use]
 mod macros;
 
-mod chunk;
+pub mod channel;
+pub mod chunk;
+pub mod controller;
+mod error;
+pub mod file;
+pub mod tempo;
 mod test_data;
-mod vlq;
+pub mod timing;
+pub mod u7;
+pub mod vlq;
+
+pub use error::{ParseError, ParseErrorKind, ParseWarning, ReadError};
+pub use file::{
+    parse_nth_track, peek_header, unwrap_rmid, BuildError, ParseOptions, SmfBuilder, SmfFile,
+};
 
 #[cfg(test)]
 extern crate quickcheck;
@@ -7,7 +7,7 @@ macro_rules! backed_enum {
     $($name:ident $(= $val:expr)?,)+
   }) => {
     $(#[$meta])*
-    #[derive(Debug, PartialEq, Eq, Clone)]
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
     #[repr($repr)]
     $vis enum $enum_name {
       $($name $(= $val)?,)*
@@ -0,0 +1,142 @@
+/// A tempo, in microseconds per quarter note, as carried by the SetTempo meta event. This is a nonzero 24-bit value.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "u32", into = "u32"))]
+pub struct Tempo(u32);
+
+impl Tempo {
+    pub const MAX_MICROSECONDS_PER_QUARTER_NOTE: u32 = 0x00FFFFFF;
+
+    const MICROSECONDS_PER_MINUTE: f64 = 60_000_000.0;
+
+    pub fn microseconds_per_quarter_note(&self) -> u32 {
+        self.0
+    }
+
+    pub fn bpm(&self) -> f64 {
+        Self::MICROSECONDS_PER_MINUTE / self.0 as f64
+    }
+
+    /// Converts beats (quarter notes) per minute to a tempo, rounding to the nearest microsecond. For example, 133 BPM
+    /// is 451,127.8 microseconds per quarter note, which rounds to 451,128.
+    /// Returns None if bpm is not a positive number, or if the result does not fit in 24 bits (i.e. below about
+    /// 3.58 BPM) or rounds to zero.
+    pub fn from_bpm(bpm: f64) -> Option<Tempo> {
+        if !bpm.is_finite() || bpm <= 0.0 {
+            return None;
+        }
+        let microseconds = (Self::MICROSECONDS_PER_MINUTE / bpm).round();
+        if microseconds < 1.0 || microseconds > Self::MAX_MICROSECONDS_PER_QUARTER_NOTE as f64 {
+            None
+        } else {
+            Some(Tempo(microseconds as u32))
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum TempoError {
+    Overflow,
+    Zero,
+}
+
+impl std::fmt::Display for TempoError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TempoError::Overflow => write!(f, "tempo must fit in 24 bits"),
+            TempoError::Zero => write!(f, "tempo must be greater than zero"),
+        }
+    }
+}
+
+impl TryFrom<u32> for Tempo {
+    type Error = TempoError;
+
+    fn try_from(n: u32) -> Result<Self, Self::Error> {
+        if n > Self::MAX_MICROSECONDS_PER_QUARTER_NOTE {
+            Err(Self::Error::Overflow)
+        } else if n == 0 {
+            Err(Self::Error::Zero)
+        } else {
+            Ok(Self(n))
+        }
+    }
+}
+
+impl From<Tempo> for u32 {
+    fn from(tempo: Tempo) -> u32 {
+        tempo.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck::Arbitrary;
+    use quickcheck_macros::quickcheck;
+
+    use super::*;
+
+    impl Arbitrary for Tempo {
+        fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+            Tempo((u32::arbitrary(g) & Tempo::MAX_MICROSECONDS_PER_QUARTER_NOTE).max(1))
+        }
+    }
+
+    #[test]
+    fn from_bpm_rounds_to_nearest() {
+        assert_eq!(
+            Tempo::from_bpm(120.0).map(u32::from),
+            Some(500_000) // Exact
+        );
+        assert_eq!(
+            Tempo::from_bpm(133.0).map(u32::from),
+            Some(451_128) // 451,127.8 rounded up
+        );
+        assert_eq!(
+            Tempo::from_bpm(70.0).map(u32::from),
+            Some(857_143) // 857,142.86 rounded up
+        );
+    }
+
+    #[test]
+    fn from_bpm_rejects_out_of_range() {
+        assert_eq!(Tempo::from_bpm(3.0), None);
+        assert_eq!(Tempo::from_bpm(0.0), None);
+        assert_eq!(Tempo::from_bpm(-120.0), None);
+        assert_eq!(Tempo::from_bpm(f64::NAN), None);
+        assert_eq!(Tempo::from_bpm(f64::INFINITY), None);
+        assert_eq!(Tempo::from_bpm(1e9), None);
+    }
+
+    #[test]
+    fn zero_is_rejected() {
+        assert_eq!(Tempo::try_from(0), Err(TempoError::Zero));
+        assert_eq!(Tempo::try_from(1).map(u32::from), Ok(1));
+    }
+
+    #[quickcheck]
+    fn round_trip_from_and_to_u32_works(n: u32) {
+        if n > 0x00FFFFFF {
+            assert_eq!(Tempo::try_from(n), Err(TempoError::Overflow));
+        } else if n == 0 {
+            assert_eq!(Tempo::try_from(n), Err(TempoError::Zero));
+        } else {
+            assert_eq!(Tempo::try_from(n).map(u32::from), Ok(n));
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_validates_range() {
+        assert_eq!(
+            serde_json::from_str::<Tempo>("500000").ok(),
+            Some(Tempo::from_bpm(120.0).expect("120 BPM is in range"))
+        );
+        assert!(serde_json::from_str::<Tempo>("16777216").is_err());
+        assert!(serde_json::from_str::<Tempo>("0").is_err());
+        assert_eq!(
+            serde_json::to_string(&Tempo::from_bpm(120.0).expect("120 BPM is in range")).ok(),
+            Some(String::from("500000"))
+        );
+    }
+}
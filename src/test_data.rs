@@ -11,5 +11,36 @@ pub(crate) mod brandenburg {
             NonZeroU16::new(11).unwrap(),
             crate::chunk::header::Division::TicksPerQuarterNote(NonZeroU16::new(1024).unwrap()),
         )
+        .unwrap()
+    }
+}
+
+/// A small, programmatically built file, so tests need not depend on the contents of brandenburg.mid.
+pub(crate) mod synthetic {
+    use std::num::NonZeroU16;
+
+    use crate::chunk::{header, track};
+
+    pub(crate) fn header() -> header::Chunk {
+        header::Chunk::new(
+            header::Format::SingleMultiChannelTrack,
+            NonZeroU16::new(1).unwrap(),
+            header::Division::TicksPerQuarterNote(NonZeroU16::new(96).unwrap()),
+        )
+        .unwrap()
+    }
+
+    pub(crate) fn track() -> track::Chunk {
+        track::Chunk::new(concat_vecs!(
+            vec![0x00, 0xFF, 0x51, 0x03, 0x07, 0xA1, 0x20], // Set tempo: 500,000 microseconds per quarter note
+            vec![0x00, 0xC0, 0x00],                         // Program change: channel 0, program 0
+            vec![0x00, 0x90, 0x3C, 0x40], // Note on: channel 0, middle C, velocity 64
+            vec![0x60, 0x80, 0x3C, 0x40], // After a quarter note, note off: channel 0, middle C, velocity 64
+            vec![0x00, 0xFF, 0x2F, 0x00]  // End of track
+        ))
+    }
+
+    pub(crate) fn data() -> Vec<u8> {
+        concat_vecs!(Vec::from(header()), Vec::from(track()))
     }
 }
@@ -0,0 +1,342 @@
+use crate::channel::Channel;
+use crate::chunk::header::Division;
+use crate::chunk::track;
+use crate::u7::U7;
+use crate::vlq::Vlq;
+
+/// A time signature taking effect at an absolute tick, with fields as in the TimeSignature meta event: denominator
+/// is a negative power of two, so 2 means quarter notes and 3 means eighth notes.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct TimeSignatureChange {
+    pub tick: u32,
+    pub numerator: u8,
+    pub denominator: u8,
+}
+
+/// A position in musical time. All fields count from zero, so the very first tick is measure 0, beat 0, tick 0.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct MeasurePosition {
+    pub measure: u32,
+    pub beat: u32,
+    pub tick_within_beat: u32,
+}
+
+/// Maps an absolute tick onto measures and beats, where a beat is the time signature's denominator note value.
+/// changes must be sorted by tick; 4/4 applies before the first change. A change partway through a measure starts
+/// a new measure. Returns None for SMPTE divisions, unsorted changes, or if any time signature has a zero-length
+/// measure or beat.
+pub fn measure_position(
+    division: &Division,
+    changes: &[TimeSignatureChange],
+    tick: u32,
+) -> Option<MeasurePosition> {
+    let ticks_per_quarter_note = match division {
+        Division::TicksPerQuarterNote(n) => n.get() as u32,
+        Division::SubdivisionsOfASecond { .. } => return None,
+    };
+    if !changes.windows(2).all(|pair| pair[0].tick <= pair[1].tick) {
+        return None;
+    }
+    let ticks_per_beat = |denominator: u8| {
+        (ticks_per_quarter_note * 4)
+            .checked_shr(denominator as u32)
+            .filter(|ticks| *ticks > 0)
+    };
+
+    let mut measure: u32 = 0;
+    let mut segment_start: u32 = 0;
+    let mut numerator: u8 = 4;
+    let mut denominator: u8 = 2;
+    for change in changes.iter().take_while(|change| change.tick <= tick) {
+        let ticks_per_measure = ticks_per_beat(denominator)? * numerator as u32;
+        if ticks_per_measure == 0 {
+            return None;
+        }
+        measure += change
+            .tick
+            .checked_sub(segment_start)?
+            .div_ceil(ticks_per_measure);
+        segment_start = change.tick;
+        numerator = change.numerator;
+        denominator = change.denominator;
+    }
+
+    let ticks_per_beat = ticks_per_beat(denominator)?;
+    let ticks_per_measure = ticks_per_beat * numerator as u32;
+    if ticks_per_measure == 0 {
+        return None;
+    }
+    let elapsed = tick - segment_start;
+    Some(MeasurePosition {
+        measure: measure + elapsed / ticks_per_measure,
+        beat: (elapsed % ticks_per_measure) / ticks_per_beat,
+        tick_within_beat: elapsed % ticks_per_beat,
+    })
+}
+
+/// The note played for each metronome click.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Click {
+    pub channel: Channel,
+    pub note: U7,
+    pub velocity: U7,
+}
+
+/// Generates a track of metronome clicks covering measures measures of the given time signature (fields as in the
+/// TimeSignature meta event). A click sounds every clocks_per_click MIDI clocks (24 clocks per quarter note), starting
+/// afresh on each downbeat, and lasts half the time until the next click or until the barline, whichever is sooner.
+/// The track ends, with an EndOfTrack, at the end of the last measure. Returns None for SMPTE divisions, zero-length
+/// measures or clicks, or a track too long to encode.
+pub fn metronome_track(
+    division: &Division,
+    numerator: u8,
+    denominator: u8,
+    clocks_per_click: u8,
+    measures: u32,
+    click: Click,
+) -> Option<track::Chunk> {
+    let ticks_per_quarter_note = match division {
+        Division::TicksPerQuarterNote(n) => n.get() as u32,
+        Division::SubdivisionsOfASecond { .. } => return None,
+    };
+    let ticks_per_measure =
+        (ticks_per_quarter_note * 4).checked_shr(denominator as u32)? * numerator as u32;
+    let ticks_per_click = ticks_per_quarter_note * clocks_per_click as u32 / 24;
+    let click_length = ticks_per_click / 2;
+    let total_ticks = ticks_per_measure.checked_mul(measures)?;
+    if ticks_per_measure == 0 || click_length == 0 {
+        return None;
+    }
+
+    let channel = click.channel.get();
+    let mut data = Vec::new();
+    let mut last_event_tick = 0;
+    for measure_start in (0..measures).map(|measure| measure * ticks_per_measure) {
+        let measure_end = measure_start + ticks_per_measure;
+        let mut tick = measure_start;
+        while tick < measure_end {
+            let end = tick.saturating_add(click_length).min(measure_end);
+            data.extend(Vlq::try_from(tick - last_event_tick).ok()?);
+            data.extend([0x90 | channel, click.note.get(), click.velocity.get()]);
+            data.extend(Vlq::try_from(end - tick).ok()?);
+            data.extend([0x80 | channel, click.note.get(), U7::MID.get()]);
+            last_event_tick = end;
+            tick = tick.saturating_add(ticks_per_click);
+        }
+    }
+    data.extend(Vlq::try_from(total_ticks - last_event_tick).ok()?);
+    data.extend([0xFF, 0x2F, 0x00]);
+
+    Some(track::Chunk::new(data))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::{NonZeroU16, NonZeroU8};
+
+    use crate::chunk::header::SMPTETimecodeFormat;
+
+    use super::*;
+
+    fn ppqn(n: u16) -> Division {
+        Division::TicksPerQuarterNote(NonZeroU16::new(n).expect("Value is non-zero"))
+    }
+
+    #[test]
+    fn defaults_to_four_four() {
+        assert_eq!(
+            measure_position(&ppqn(480), &[], 480 * 4 + 480 * 2 + 5),
+            Some(MeasurePosition {
+                measure: 1,
+                beat: 2,
+                tick_within_beat: 5,
+            })
+        );
+    }
+
+    #[test]
+    fn four_four_then_three_four() {
+        let changes = [
+            TimeSignatureChange {
+                tick: 0,
+                numerator: 4,
+                denominator: 2,
+            },
+            TimeSignatureChange {
+                tick: 480 * 4 * 2,
+                numerator: 3,
+                denominator: 2,
+            },
+        ];
+        assert_eq!(
+            measure_position(&ppqn(480), &changes, 480 * 4 + 480 * 3),
+            Some(MeasurePosition {
+                measure: 1,
+                beat: 3,
+                tick_within_beat: 0,
+            })
+        );
+        assert_eq!(
+            measure_position(&ppqn(480), &changes, 480 * 4 * 2),
+            Some(MeasurePosition {
+                measure: 2,
+                beat: 0,
+                tick_within_beat: 0,
+            })
+        );
+        assert_eq!(
+            measure_position(&ppqn(480), &changes, 480 * 4 * 2 + 480 * 3 + 480 * 2 + 10),
+            Some(MeasurePosition {
+                measure: 3,
+                beat: 2,
+                tick_within_beat: 10,
+            })
+        );
+    }
+
+    #[test]
+    fn eighth_note_beats() {
+        let changes = [TimeSignatureChange {
+            tick: 0,
+            numerator: 7,
+            denominator: 3,
+        }];
+        assert_eq!(
+            measure_position(&ppqn(480), &changes, 240 * 7 + 240 * 6 + 1),
+            Some(MeasurePosition {
+                measure: 1,
+                beat: 6,
+                tick_within_beat: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn zero_length_measure_before_a_later_change_is_rejected() {
+        let changes = [
+            TimeSignatureChange {
+                tick: 0,
+                numerator: 0,
+                denominator: 2,
+            },
+            TimeSignatureChange {
+                tick: 480,
+                numerator: 4,
+                denominator: 2,
+            },
+        ];
+        assert_eq!(measure_position(&ppqn(480), &changes, 960), None);
+    }
+
+    #[test]
+    fn unsorted_changes_are_rejected() {
+        let changes = [
+            TimeSignatureChange {
+                tick: 960,
+                numerator: 3,
+                denominator: 2,
+            },
+            TimeSignatureChange {
+                tick: 480,
+                numerator: 4,
+                denominator: 2,
+            },
+        ];
+        assert_eq!(measure_position(&ppqn(480), &changes, 1920), None);
+    }
+
+    #[test]
+    fn unsorted_changes_after_tick_are_rejected() {
+        let change = |tick| TimeSignatureChange {
+            tick,
+            numerator: 3,
+            denominator: 2,
+        };
+        let changes = [change(480), change(2000), change(960)];
+        assert_eq!(measure_position(&ppqn(480), &changes, 1000), None);
+    }
+
+    #[test]
+    fn metronome_for_one_four_four_measure() {
+        let click = Click {
+            channel: Channel::from_nibble(9),
+            note: U7::try_from(37).expect("Value is in range"),
+            velocity: U7::MAX,
+        };
+        let on = [0x99, 37, 0x7F];
+        let off = [0x89, 37, 0x40];
+        assert_eq!(
+            metronome_track(&ppqn(96), 4, 2, 24, 1, click),
+            Some(track::Chunk::new(concat_vecs!(
+                [0x00],
+                on,
+                [0x30],
+                off,
+                [0x30],
+                on,
+                [0x30],
+                off,
+                [0x30],
+                on,
+                [0x30],
+                off,
+                [0x30],
+                on,
+                [0x30],
+                off,
+                [0x30, 0xFF, 0x2F, 0x00]
+            )))
+        );
+    }
+
+    #[test]
+    fn metronome_clicks_restart_at_each_measure() {
+        let click = Click {
+            channel: Channel::from_nibble(9),
+            note: U7::try_from(37).expect("Value is in range"),
+            velocity: U7::MAX,
+        };
+        let on = [0x99, 37, 0x7F];
+        let off = [0x89, 37, 0x40];
+        // 5/8 is 240 ticks, which is not a whole number of 96-tick (quarter note) clicks.
+        assert_eq!(
+            metronome_track(&ppqn(96), 5, 3, 24, 2, click),
+            Some(track::Chunk::new(concat_vecs!(
+                [0x00], // Tick 0
+                on,
+                [0x30],
+                off,
+                [0x30], // Tick 96
+                on,
+                [0x30],
+                off,
+                [0x30], // Tick 192
+                on,
+                [0x30],
+                off,
+                [0x00], // Tick 240: the downbeat of the second measure
+                on,
+                [0x30],
+                off,
+                [0x30], // Tick 336
+                on,
+                [0x30],
+                off,
+                [0x30], // Tick 432
+                on,
+                [0x30],
+                off,
+                [0x00, 0xFF, 0x2F, 0x00]
+            )))
+        );
+    }
+
+    #[test]
+    fn smpte_is_unsupported() {
+        let division = Division::SubdivisionsOfASecond {
+            timecode_format: SMPTETimecodeFormat::TwentyFive,
+            ticks_per_frame: NonZeroU8::new(40).expect("Value is non-zero"),
+        };
+        assert_eq!(measure_position(&division, &[], 0), None);
+    }
+}
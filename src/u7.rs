@@ -0,0 +1,144 @@
+/// A 7-bit unsigned integer, as used by most MIDI data bytes (note numbers, velocities, controller values, etc).
+/// Values must be less than or equal to U7::MAX.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "u8", into = "u8"))]
+pub struct U7(u8);
+
+impl U7 {
+    pub const MIN: U7 = U7(0x00);
+    pub const MID: U7 = U7(0x40);
+    pub const MAX: U7 = U7(0x7F);
+
+    /// Like U7::try_from(n), but usable in constants.
+    pub const fn new(n: u8) -> Option<U7> {
+        if n > U7::MAX.0 {
+            None
+        } else {
+            Some(U7(n))
+        }
+    }
+
+    pub fn get(&self) -> u8 {
+        self.0
+    }
+
+    /// Maps a proportion in 0.0..=1.0 onto 0..=127, rounding to the nearest value.
+    /// Inputs outside that range are clamped, and NaN maps to U7::MIN.
+    pub fn percent(p: f64) -> U7 {
+        if p.is_nan() {
+            U7::MIN
+        } else {
+            U7((p.clamp(0.0, 1.0) * U7::MAX.0 as f64).round() as u8)
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum U7Error {
+    Overflow,
+}
+
+impl std::fmt::Display for U7Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            U7Error::Overflow => write!(f, "value must be between 0 and 127"),
+        }
+    }
+}
+
+impl TryFrom<u8> for U7 {
+    type Error = U7Error;
+
+    fn try_from(n: u8) -> Result<Self, Self::Error> {
+        U7::new(n).ok_or(Self::Error::Overflow)
+    }
+}
+
+impl TryFrom<u16> for U7 {
+    type Error = U7Error;
+
+    fn try_from(n: u16) -> Result<Self, Self::Error> {
+        u8::try_from(n)
+            .map_err(|_| Self::Error::Overflow)
+            .and_then(U7::try_from)
+    }
+}
+
+impl TryFrom<i32> for U7 {
+    type Error = U7Error;
+
+    fn try_from(n: i32) -> Result<Self, Self::Error> {
+        u8::try_from(n)
+            .map_err(|_| Self::Error::Overflow)
+            .and_then(U7::try_from)
+    }
+}
+
+impl From<U7> for u8 {
+    fn from(n: U7) -> u8 {
+        n.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck::Arbitrary;
+    use quickcheck_macros::quickcheck;
+
+    use super::*;
+
+    impl Arbitrary for U7 {
+        fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+            U7(u8::arbitrary(g) & U7::MAX.0)
+        }
+    }
+
+    #[test]
+    fn constants_are_correct() {
+        assert_eq!(U7::MIN.get(), 0);
+        assert_eq!(U7::MID.get(), 64);
+        assert_eq!(U7::MAX.get(), 127);
+    }
+
+    #[test]
+    fn percent_maps_onto_range() {
+        assert_eq!(U7::percent(0.0), U7::MIN);
+        assert_eq!(U7::percent(0.5), U7::MID);
+        assert_eq!(U7::percent(1.0), U7::MAX);
+        assert_eq!(U7::percent(-0.5), U7::MIN);
+        assert_eq!(U7::percent(2.0), U7::MAX);
+        assert_eq!(U7::percent(f64::NAN), U7::MIN);
+    }
+
+    #[quickcheck]
+    fn round_trip_from_and_to_u8_works(n: u8) {
+        if n > 0x7F {
+            assert_eq!(U7::try_from(n), Err(U7Error::Overflow));
+        } else {
+            assert_eq!(U7::try_from(n).map(u8::from), Ok(n));
+        }
+    }
+
+    #[test]
+    fn try_from_wider_integers_checks_range() {
+        assert_eq!(U7::try_from(-1i32), Err(U7Error::Overflow));
+        assert_eq!(U7::try_from(127i32), Ok(U7::MAX));
+        assert_eq!(U7::try_from(128i32), Err(U7Error::Overflow));
+        assert_eq!(U7::try_from(i32::MIN), Err(U7Error::Overflow));
+        assert_eq!(U7::try_from(127u16), Ok(U7::MAX));
+        assert_eq!(U7::try_from(128u16), Err(U7Error::Overflow));
+        assert_eq!(U7::try_from(0x0100u16), Err(U7Error::Overflow));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_validates_range() {
+        assert_eq!(serde_json::from_str::<U7>("127").ok(), Some(U7::MAX));
+        assert!(serde_json::from_str::<U7>("200").is_err());
+        assert_eq!(
+            serde_json::to_string(&U7::MAX).ok(),
+            Some(String::from("127"))
+        );
+    }
+}
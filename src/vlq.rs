@@ -4,7 +4,7 @@ pub const MAX_REPRESENTABLE: u32 = 0x0FFFFFFF;
 
 /// Variable length quantity encoding of integers.
 /// Integers must be less than or equal to MAX_REPRESENTABLE.
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[derive(PartialEq, Eq, Clone, Copy)]
 pub struct Vlq {
     bytes: [u8; 4],
     size: usize,
@@ -14,11 +14,24 @@ impl Vlq {
     pub fn get(&self) -> u32 {
         u32::from(self)
     }
+
+    /// Reads a variable length quantity from the start of bytes, returning it along with the unconsumed remainder.
+    /// Returns None if bytes ends mid-quantity, or if the quantity runs past the four bytes the spec allows.
+    pub(crate) fn parse(bytes: &[u8]) -> Option<(Self, &[u8])> {
+        let mut value: u32 = 0;
+        for (i, byte) in bytes.iter().take(4).enumerate() {
+            value = (value << 7) | (byte & 0x7F) as u32;
+            if byte & 0x80 == 0 {
+                return Some((Self::try_from(value).ok()?, &bytes[(i + 1)..]));
+            }
+        }
+        None
+    }
 }
 
 impl PartialOrd for Vlq {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        u32::from(self).partial_cmp(&u32::from(other))
+        Some(self.cmp(other))
     }
 }
 
@@ -34,6 +47,20 @@ impl Display for Vlq {
     }
 }
 
+impl Debug for Vlq {
+    /// Shows both the decoded value and the encoded bytes, e.g. Vlq(0x000000FF = [0x81, 0x7F]).
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Vlq({} = [", self)?;
+        for (i, byte) in self.into_iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{:#04X}", byte)?;
+        }
+        write!(f, "])")
+    }
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub enum VLQError {
     OverMaxSize,
@@ -62,6 +89,17 @@ impl TryFrom<u32> for Vlq {
     }
 }
 
+impl TryFrom<usize> for Vlq {
+    type Error = VLQError;
+
+    /// Checks the range before narrowing, so lengths which do not fit in a u32 are rejected rather than truncated.
+    fn try_from(n: usize) -> Result<Self, Self::Error> {
+        u32::try_from(n)
+            .map_err(|_| Self::Error::OverMaxSize)
+            .and_then(Vlq::try_from)
+    }
+}
+
 impl From<Vlq> for u32 {
     fn from(n: Vlq) -> u32 {
         let mut result: u32 = 0;
@@ -118,84 +156,84 @@ mod tests {
     #[test]
     fn byte_representations_are_correct() {
         assert_eq!(
-            Vlq::try_from(0x00000000)
+            Vlq::try_from(0x00000000u32)
                 .expect("Value is in spec!")
                 .into_iter()
                 .collect::<Vec<u8>>(),
             vec![0x00]
         );
         assert_eq!(
-            Vlq::try_from(0x00000040)
+            Vlq::try_from(0x00000040u32)
                 .expect("Value is in spec!")
                 .into_iter()
                 .collect::<Vec<u8>>(),
             vec![0x40]
         );
         assert_eq!(
-            Vlq::try_from(0x0000007F)
+            Vlq::try_from(0x0000007Fu32)
                 .expect("Value is in spec!")
                 .into_iter()
                 .collect::<Vec<u8>>(),
             vec![0x7F]
         );
         assert_eq!(
-            Vlq::try_from(0x00000080)
+            Vlq::try_from(0x00000080u32)
                 .expect("Value is in spec!")
                 .into_iter()
                 .collect::<Vec<u8>>(),
             vec![0x81, 0x00]
         );
         assert_eq!(
-            Vlq::try_from(0x00002000)
+            Vlq::try_from(0x00002000u32)
                 .expect("Value is in spec!")
                 .into_iter()
                 .collect::<Vec<u8>>(),
             vec![0xC0, 0x00]
         );
         assert_eq!(
-            Vlq::try_from(0x00003FFF)
+            Vlq::try_from(0x00003FFFu32)
                 .expect("Value is in spec!")
                 .into_iter()
                 .collect::<Vec<u8>>(),
             vec![0xFF, 0x7F]
         );
         assert_eq!(
-            Vlq::try_from(0x00004000)
+            Vlq::try_from(0x00004000u32)
                 .expect("Value is in spec!")
                 .into_iter()
                 .collect::<Vec<u8>>(),
             vec![0x81, 0x80, 0x00]
         );
         assert_eq!(
-            Vlq::try_from(0x00100000)
+            Vlq::try_from(0x00100000u32)
                 .expect("Value is in spec!")
                 .into_iter()
                 .collect::<Vec<u8>>(),
             vec![0xC0, 0x80, 0x00]
         );
         assert_eq!(
-            Vlq::try_from(0x001FFFFF)
+            Vlq::try_from(0x001FFFFFu32)
                 .expect("Value is in spec!")
                 .into_iter()
                 .collect::<Vec<u8>>(),
             vec![0xFF, 0xFF, 0x7F]
         );
         assert_eq!(
-            Vlq::try_from(0x00200000)
+            Vlq::try_from(0x00200000u32)
                 .expect("Value is in spec!")
                 .into_iter()
                 .collect::<Vec<u8>>(),
             vec![0x81, 0x80, 0x80, 0x00]
         );
         assert_eq!(
-            Vlq::try_from(0x08000000)
+            Vlq::try_from(0x08000000u32)
                 .expect("Value is in spec!")
                 .into_iter()
                 .collect::<Vec<u8>>(),
             vec![0xC0, 0x80, 0x80, 0x00]
         );
         assert_eq!(
-            Vlq::try_from(0x0FFFFFFF)
+            Vlq::try_from(0x0FFFFFFFu32)
                 .expect("Value is in spec!")
                 .into_iter()
                 .collect::<Vec<u8>>(),
@@ -203,6 +241,64 @@ mod tests {
         );
     }
 
+    /// The derived PartialEq compares the encoded bytes, which is only correct because every value has exactly one
+    /// encoding (unused bytes are always zero). This guards that assumption.
+    #[quickcheck]
+    fn equality_matches_value_and_bytes(a: Vlq, b: Vlq) {
+        assert_eq!(Vlq::try_from(a.get()), Ok(a));
+        assert_eq!(a == b, a.get() == b.get());
+        assert_eq!(
+            a == b,
+            a.into_iter().collect::<Vec<u8>>() == b.into_iter().collect::<Vec<u8>>()
+        );
+    }
+
+    #[test]
+    fn debug_shows_value_and_bytes() {
+        assert_eq!(
+            format!("{:?}", Vlq::try_from(0xFFu32).expect("Value is in spec!")),
+            "Vlq(0x000000FF = [0x81, 0x7F])"
+        );
+        assert_eq!(
+            format!("{:?}", Vlq::try_from(0u32).expect("Value is in spec!")),
+            "Vlq(0x00000000 = [0x00])"
+        );
+    }
+
+    #[test]
+    fn try_from_usize_checks_range() {
+        assert_eq!(
+            Vlq::try_from(MAX_REPRESENTABLE as usize).map(u32::from),
+            Ok(MAX_REPRESENTABLE)
+        );
+        assert_eq!(
+            Vlq::try_from(MAX_REPRESENTABLE as usize + 1),
+            Err(VLQError::OverMaxSize)
+        );
+        assert_eq!(Vlq::try_from(usize::MAX), Err(VLQError::OverMaxSize));
+    }
+
+    #[test]
+    fn parse_returns_remainder() {
+        assert_eq!(
+            Vlq::parse(&[0x81, 0x7F, 0xAB]),
+            Some((
+                Vlq::try_from(0xFFu32).expect("Value is in spec!"),
+                &[0xAB][..]
+            ))
+        );
+        assert_eq!(Vlq::parse(&[0x81]), None);
+        assert_eq!(Vlq::parse(&[0xFF, 0xFF, 0xFF, 0xFF, 0x7F]), None);
+    }
+
+    #[quickcheck]
+    fn parse_reads_serialized_bytes(n: Vlq) {
+        assert_eq!(
+            Vlq::parse(&n.into_iter().collect::<Vec<u8>>()),
+            Some((n, &[][..]))
+        );
+    }
+
     #[quickcheck]
     fn round_trip_from_and_to_u32_works(n: u32) {
         if n > MAX_REPRESENTABLE {